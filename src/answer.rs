@@ -0,0 +1,143 @@
+//! Answers given by solutions through [`Context::result`](crate::Context::result).
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    /// Single-line text
+    Text(String),
+    /// Multi-line text, e.g. letters drawn in ASCII art that have to be read by a human
+    Art(String),
+    /// The part has no answer, e.g. the last day has no part 2
+    NotApplicable,
+}
+
+impl Answer {
+    /// Builds an ASCII art answer from its rows
+    pub fn art<R: AsRef<str>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.as_ref().to_string())
+            .collect::<Vec<_>>();
+        Answer::Art(rows.join("\n"))
+    }
+
+    /// Whether this answer can be typed into the puzzle website as-is
+    pub fn is_submittable(&self) -> bool {
+        matches!(self, Answer::Integer(_) | Answer::Text(_))
+    }
+
+    /// Whether both answers are the same, regardless of how they were built: texts match by content, and a text
+    /// matches an integer written out the same way, e.g. `Text("123")` matches `Integer(123)`.
+    /// No answer only matches [`Answer::NotApplicable`]
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(text) | Answer::Art(text), Answer::Text(other) | Answer::Art(other)) => {
+                text == other
+            }
+            (Answer::Integer(value), Answer::Text(text))
+            | (Answer::Text(text), Answer::Integer(value)) => *text == value.to_string(),
+            _ => self == other,
+        }
+    }
+}
+
+/// Inverse of [`FromStr`], except that text looking like another kind of answer is parsed as that kind
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) | Answer::Art(text) => f.write_str(text),
+            Answer::NotApplicable => f.write_str("n/a"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Parses a stored or expected answer: `n/a` for no answer, integers, and multi-line text as art
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(if text == "n/a" {
+            Answer::NotApplicable
+        } else if let Ok(value) = text.parse() {
+            Answer::Integer(value)
+        } else if text.contains('\n') {
+            Answer::Art(text.to_string())
+        } else {
+            Answer::Text(text.to_string())
+        })
+    }
+}
+
+macro_rules! from_integer {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(value: $type) -> Self {
+                    Answer::Integer(value.try_into().expect("answer does not fit in an i128"))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::Art(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trip() {
+        let answers = [
+            Answer::Integer(-42),
+            Answer::Text("abc".to_string()),
+            Answer::art(["#..#", "####"]),
+            Answer::NotApplicable,
+        ];
+        for answer in answers {
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(Answer::from(7u64), Answer::Integer(7));
+        assert_eq!(Answer::from("a\nb"), Answer::Art("a\nb".to_string()));
+        assert!(!Answer::NotApplicable.is_submittable());
+    }
+
+    #[test]
+    fn matches_written_out() {
+        assert!(Answer::from("123").matches(&Answer::Integer(123)));
+        assert!(Answer::Integer(-4).matches(&"-4".parse().unwrap()));
+        assert!(Answer::Text("a\nb".to_string()).matches(&Answer::art(["a", "b"])));
+        assert!(!Answer::from("123").matches(&Answer::Integer(124)));
+        assert_eq!("".parse::<Answer>().unwrap(), Answer::Text(String::new()));
+        assert!(!Answer::from("").matches(&Answer::NotApplicable));
+        assert!(!Answer::from("n/a").matches(&Answer::NotApplicable));
+        assert!(!Answer::from("0123").matches(&Answer::Integer(123)));
+    }
+}
@@ -0,0 +1,169 @@
+//! Optional runner configuration, read from `aoc.toml` in the working directory.
+//! Command line flags are applied afterwards, so they take precedence over the file.
+//!
+//! ```toml
+//! data_dir = "data"
+//! year = 2025
+//! run_type = "examples"   # used when no mode is given: "full" or "examples"
+//! format = "json"         # "text" or "json"
+//! color = false           # defaults to color only when writing to a terminal
+//! timeout = 10            # seconds, for every day
+//! session = "53616c74..."
+//!
+//! [days.8]
+//! timeout = 30
+//! params = { connections = 500 }
+//! ```
+
+use crate::{OutputFormat, RunOptions, RunType};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub data_dir: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub run_type: Option<RunType>,
+    pub format: Option<OutputFormat>,
+    pub color: Option<bool>,
+    /// Seconds
+    pub timeout: Option<f64>,
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    /// Keyed by day number
+    #[serde(default)]
+    pub days: HashMap<String, DayConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayConfig {
+    /// Seconds, replaces the global timeout for this day
+    pub timeout: Option<f64>,
+    /// Values for [`Context::param`](crate::Context::param)
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("could not read config file")]
+    Io(#[from] std::io::Error),
+    #[error("invalid config file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("invalid day \"{0}\" in config file")]
+    InvalidDay(String),
+}
+
+impl Config {
+    /// Reads the config file, returning the default config if it doesn't exist
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(text)?;
+        if let Some(day) = config.days.keys().find(|day| day.parse::<u8>().is_err()) {
+            return Err(ConfigError::InvalidDay(day.clone()));
+        }
+        Ok(config)
+    }
+
+    /// Applies the settings that are part of the run options
+    pub fn apply(&self, options: &mut RunOptions) {
+        if let Some(data_dir) = &self.data_dir {
+            options.data_dir = data_dir.clone();
+        }
+        if let Some(format) = self.format {
+            options.format = format;
+        }
+        if self.color.is_some() {
+            options.color = self.color;
+        }
+        if let Some(timeout) = self.timeout {
+            options.timeout = Some(Duration::from_secs_f64(timeout));
+        }
+        let server = &mut options.inputs.server;
+        if let Some(year) = self.year {
+            server.year = year;
+        }
+        if let Some(base_url) = &self.base_url {
+            server.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if self.session.is_some() {
+            server.session = self.session.clone();
+        }
+        for (day, config) in &self.days {
+            let overrides = options.days.entry(day.parse().unwrap()).or_default();
+            if let Some(timeout) = config.timeout {
+                overrides.timeout = Some(Duration::from_secs_f64(timeout));
+            }
+            overrides
+                .params
+                .extend(config.params.iter().map(|(name, value)| {
+                    let value = match value {
+                        toml::Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    };
+                    (name.clone(), value)
+                }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_config() {
+        let config = Config::parse(
+            r#"
+            data_dir = "inputs"
+            run_type = "examples"
+            format = "json"
+            timeout = 2.5
+
+            [days.8]
+            timeout = 30
+            params = { connections = 500, name = "x" }
+            "#,
+        )
+        .unwrap();
+        let mut options = RunOptions::default();
+        config.apply(&mut options);
+
+        assert_eq!(config.run_type, Some(RunType::Examples));
+        assert_eq!(options.day_dir(3), Path::new("inputs/3"));
+        assert_eq!(options.format, OutputFormat::Json);
+        assert_eq!(options.timeout_for(1), Some(Duration::from_millis(2500)));
+        assert_eq!(options.timeout_for(8), Some(Duration::from_secs(30)));
+        let params = &options.days[&8].params;
+        assert_eq!(params["connections"], "500");
+        assert_eq!(params["name"], "x");
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(matches!(
+            Config::parse("colour = true"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Config::parse("[days.first]\ntimeout = 1"),
+            Err(ConfigError::InvalidDay(_))
+        ));
+    }
+}
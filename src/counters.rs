@@ -0,0 +1,55 @@
+//! Named operation counters that solutions increment through [`Context::count`](crate::Context::count), e.g. the
+//! number of recursive calls or of segments processed. They are collected per part, and shown next to each part's
+//! answer in the report.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Counter values of a single part, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Counts(BTreeMap<&'static str, u64>);
+
+impl Counts {
+    pub(crate) fn add(&mut self, name: &'static str, amount: u64) {
+        *self.0.entry(name).or_default() += amount;
+    }
+
+    /// Value of a counter, 0 if it was never incremented
+    pub fn get(&self, name: &str) -> u64 {
+        self.0.get(name).copied().unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        self.0.iter().map(|(&name, &value)| (name, value))
+    }
+}
+
+impl Display for Counts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let counts = self
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", counts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_by_name() {
+        let mut counts = Counts::default();
+        counts.add("segments", 2);
+        counts.add("calls", 1);
+        counts.add("segments", 3);
+        assert_eq!((counts.get("segments"), counts.get("missing")), (5, 0));
+        assert_eq!(counts.to_string(), "calls=1 segments=5");
+    }
+}
@@ -1,8 +1,14 @@
-use crate::{debug_example, Context, DayInfo};
+use crate::answer::Answer;
+use crate::generate::InputGenerator;
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::solution::Solution;
+use crate::utils::random::Rng;
+use crate::{trace, Context, DayInfo};
 
 pub const INFO: DayInfo = DayInfo {
     name: "Secret Entrance",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 L68
 L30
@@ -14,18 +20,37 @@ L1
 L99
 R14
 L82",
+    generator: Some(&Generator),
+    validator: None,
+    streaming: true,
+    variants: &[&ClickByClick],
 };
 
+/// Rotation in clicks, negative to the left
+fn parse_rotation(number: usize, line: &str) -> Result<i32, ParseError> {
+    let mut chars = line.chars();
+    let multiplier = match chars.next() {
+        Some('L') => -1,
+        Some('R') => 1,
+        _ => return Err(ParseError::new(number, "expected L or R")),
+    };
+    let amount = parse::number::<i32>(number, chars.as_str())?;
+    if amount < 0 {
+        return Err(ParseError::new(number, "negative amount"));
+    }
+    Ok(amount * multiplier)
+}
+
+fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    numbered_lines(input)
+        .map(|(number, line)| parse_rotation(number, line))
+        .collect()
+}
+
 fn run(context: &mut Context) {
-    let rotations = context.input.lines().map(|line| {
-        let multiplier = if line.chars().next().unwrap() == 'L' {
-            -1
-        } else {
-            1
-        };
-        let amount = line[1..].parse::<i32>().unwrap();
-        amount * multiplier
-    });
+    // Streamed, both parts are counted in a single pass; part 1's time, memory and counters therefore cover both
+    // parts, and part 2's are next to nothing
+    let rotations = context.parse_lines(parse_rotation);
     let mut dial = 50;
     let mut counter1 = 0;
     let mut counter2 = 0;
@@ -33,7 +58,7 @@ fn run(context: &mut Context) {
         let new_dial = dial + rotation;
         let final_dial = new_dial.rem_euclid(100);
         if final_dial == 0 {
-            debug_example!(context, "(1)  {rotation}");
+            trace!(context, "(1)  {rotation}");
             counter1 += 1;
         }
         let zero_counts = if new_dial > 0 {
@@ -44,7 +69,7 @@ fn run(context: &mut Context) {
             1 + new_dial / -100
         };
         if zero_counts != 0 {
-            debug_example!(context, "(2)  {rotation} - {zero_counts}");
+            trace!(context, "(2)  {rotation} - {zero_counts}");
         }
         counter2 += zero_counts;
         dial = final_dial;
@@ -52,3 +77,76 @@ fn run(context: &mut Context) {
     context.result(counter1);
     context.result(counter2);
 }
+
+/// Turns the dial one click at a time, counting every time it lands on 0
+struct ClickByClick;
+
+impl ClickByClick {
+    /// Times the dial points at 0 at the end of a rotation, and during any click
+    fn count_zeros(rotations: &[i32]) -> (u32, u32) {
+        let mut dial = 50;
+        let (mut at_end, mut during) = (0, 0);
+        for &rotation in rotations {
+            for _ in 0..rotation.abs() {
+                dial = (dial + rotation.signum()).rem_euclid(100);
+                if dial == 0 {
+                    during += 1;
+                }
+            }
+            if dial == 0 {
+                at_end += 1;
+            }
+        }
+        (at_end, during)
+    }
+}
+
+impl Solution for ClickByClick {
+    type Input = Vec<i32>;
+
+    fn name(&self) -> &'static str {
+        "click-by-click"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, rotations: &Vec<i32>, _context: &Context) -> Answer {
+        Self::count_zeros(rotations).0.into()
+    }
+
+    fn part2(&self, rotations: &Vec<i32>, _context: &Context) -> Answer {
+        Self::count_zeros(rotations).1.into()
+    }
+}
+
+/// Random rotations of up to 999 clicks, a quarter of which end at 0 like in the real input;
+/// `size` is the number of rotations
+struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut dial = 50;
+        (0..size)
+            .map(|_| {
+                let rotation = if dial != 0 && rng.chance(0.25) {
+                    // Back to 0, possibly after some full turns
+                    let (left, right) = (-dial, 100 - dial);
+                    let turns = 100 * rng.range(0..=8);
+                    if rng.chance(0.5) {
+                        left - turns
+                    } else {
+                        right + turns
+                    }
+                } else {
+                    rng.range(1..=999) * if rng.chance(0.5) { -1 } else { 1 }
+                };
+                dial = (dial + rotation).rem_euclid(100);
+                let direction = if rotation < 0 { 'L' } else { 'R' };
+                format!("{direction}{}", rotation.abs())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,24 +1,33 @@
 use crate::Part::One;
-use crate::{Context, DayInfo, debug_example};
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::utils::numeric::lcm;
+use crate::{Context, DayInfo, debug, trace};
 use regex::Regex;
 use std::cmp::min;
 use std::fmt::Display;
-use std::mem::swap;
 use std::sync::LazyLock;
 
 pub const INFO: DayInfo = DayInfo {
     name: "Factory",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+    generator: None,
+    validator: None,
+    streaming: false,
+    variants: &[],
 };
 
 static LINE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^\[([.#]+?)]((?: \(\d+(?:,\d+)*\))+) \{(\d+(?:,\d+)*)}$").unwrap()
 });
 
+/// The runner gave up on the day while a machine was being solved
+struct Cancelled;
+
 #[derive(Debug, Clone)]
 struct Machine {
     indicators: Vec<bool>,
@@ -26,62 +35,57 @@ struct Machine {
     joltages: Vec<usize>,
 }
 
-#[allow(dead_code)]
-fn print_matrix(matrix: &Vec<Vec<impl Display>>) {
-    for row in matrix.iter() {
-        for num in row {
-            print!("{num}\t");
-        }
-        println!();
-    }
+fn format_matrix(matrix: &[Vec<impl Display>]) -> String {
+    matrix
+        .iter()
+        .map(|row| row.iter().map(|num| format!("{num}\t")).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-/// Calculate the greatest common divisor
-fn gcd(mut a: i32, mut b: i32) -> i32 {
-    if b > a {
-        swap(&mut a, &mut b);
-    }
-    loop {
-        let r = a % b;
-        if r == 0 {
-            return b;
-        }
-        a = b;
-        b = r;
-    }
-}
-
-/// Calculate the least common multiple
-fn lcm(a: i32, b: i32) -> i32 {
-    a * b / gcd(a, b)
+fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    numbered_lines(input)
+        .map(|(number, line)| {
+            let r = LINE_PATTERN
+                .captures(line)
+                .ok_or_else(|| ParseError::new(number, "invalid machine"))?;
+            let indicators = r[1].chars().map(|c| c == '#').collect::<Vec<_>>();
+            let buttons = r[2]
+                .trim_start()
+                .split(' ')
+                .map(|s| {
+                    // The pattern guarantees the parentheses
+                    parse::numbers::<usize>(number, &s[1..s.len() - 1], ',')
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if buttons
+                .iter()
+                .flatten()
+                .any(|&index| index >= indicators.len())
+            {
+                return Err(ParseError::new(
+                    number,
+                    "button wired to a missing indicator",
+                ));
+            }
+            let joltages = parse::numbers(number, &r[3], ',')?;
+            if joltages.len() != indicators.len() {
+                return Err(ParseError::new(
+                    number,
+                    "expected one joltage per indicator",
+                ));
+            }
+            Ok(Machine {
+                indicators,
+                buttons,
+                joltages,
+            })
+        })
+        .collect()
 }
 
 fn run(context: &mut Context) {
-    let machines = context
-        .input
-        .lines()
-        .map(|line| {
-            let r = LINE_PATTERN.captures(line).unwrap();
-            Machine {
-                indicators: r[1].chars().map(|c| c == '#').collect::<Vec<_>>(),
-                buttons: r[2]
-                    .trim_start()
-                    .split(' ')
-                    .map(|s| {
-                        assert!(s.starts_with('(') && s.ends_with(')'));
-                        s[1..s.len() - 1]
-                            .split(',')
-                            .map(|s| s.parse::<usize>().unwrap())
-                            .collect::<Vec<_>>()
-                    })
-                    .collect::<Vec<_>>(),
-                joltages: r[3]
-                    .split(',')
-                    .map(|s| s.parse::<usize>().unwrap())
-                    .collect::<Vec<_>>(),
-            }
-        })
-        .collect::<Vec<_>>();
+    let machines = context.parse(parse);
 
     fn try_indicator_buttons(
         indicators: &Vec<bool>,
@@ -131,7 +135,9 @@ fn run(context: &mut Context) {
     context.result(
         machines
             .iter()
-            .map(|machine| {
+            .enumerate()
+            .map(|(index, machine)| {
+                context.progress(index, machines.len());
                 try_indicator_buttons(
                     &vec![false; machine.indicators.len()],
                     &machine.indicators,
@@ -147,11 +153,7 @@ fn run(context: &mut Context) {
         return;
     }
 
-    fn solve_machine(
-        #[allow(unused_variables)] context: &Context,
-        #[allow(unused_variables)] index: usize,
-        machine: &Machine,
-    ) -> u32 {
+    fn solve_machine(context: &Context, index: usize, machine: &Machine) -> Result<u32, Cancelled> {
         // Convert buttons into system of equations
         let mut matrix = machine
             .joltages
@@ -168,11 +170,12 @@ fn run(context: &mut Context) {
             })
             .collect::<Vec<_>>();
 
-        #[cfg(debug_assertions)]
-        if context.run_type == crate::RunType::Examples {
-            println!("\nInitial matrix [{}]:", index + 1);
-            print_matrix(&matrix);
-        }
+        trace!(
+            context,
+            "initial matrix [{}]:\n{}",
+            index + 1,
+            format_matrix(&matrix)
+        );
 
         // Solve system using Gaussian elimination
         let mut i = 0;
@@ -266,11 +269,12 @@ fn run(context: &mut Context) {
             j += 1;
         }
 
-        #[cfg(debug_assertions)]
-        if context.run_type == crate::RunType::Examples {
-            println!("Row echelon form [{}]:", index + 1);
-            print_matrix(&matrix);
-        }
+        trace!(
+            context,
+            "row echelon form [{}]:\n{}",
+            index + 1,
+            format_matrix(&matrix)
+        );
 
         // Finally, back-track through all solutions. This could definitely be optimized,
         // but it runs in ~0.01s (~1.5s for full input), so it's fine. Too much work already
@@ -284,13 +288,18 @@ fn run(context: &mut Context) {
             column: usize,
             remaining_sum: Option<i32>,
             button_presses: Vec<Option<u32>>,
-        ) -> Option<u32> {
+        ) -> Result<Option<u32>, Cancelled> {
+            context.count("backtrack_calls");
+            // Some machines take a while, give up on the whole search if the runner isn't waiting anymore
+            if context.is_cancelled() {
+                return Err(Cancelled);
+            }
             if row < 0 {
                 let sum = button_presses
                     .iter()
                     .filter_map(|&button| button)
                     .sum::<u32>();
-                debug_example!(
+                trace!(
                     context,
                     "Found {sum}: {:?}",
                     button_presses
@@ -298,7 +307,7 @@ fn run(context: &mut Context) {
                         .map(Option::unwrap)
                         .collect::<Vec<_>>()
                 );
-                return Some(sum);
+                return Ok(Some(sum));
             }
             let remaining_sum = match remaining_sum {
                 Some(sum) => sum,
@@ -317,11 +326,11 @@ fn run(context: &mut Context) {
                         if remaining_sum % buttons[button] == 0 {
                             let presses = remaining_sum / buttons[button];
                             if presses < 0 {
-                                None
+                                Ok(None)
                             } else if let Some(known_presses) = button_presses[button]
                                 && known_presses != presses as u32
                             {
-                                None
+                                Ok(None)
                             } else {
                                 let mut button_presses = button_presses.clone();
                                 button_presses[button] = Some(presses as u32);
@@ -336,7 +345,7 @@ fn run(context: &mut Context) {
                                 )
                             }
                         } else {
-                            None
+                            Ok(None)
                         }
                     } else {
                         // Other buttons left
@@ -373,15 +382,15 @@ fn run(context: &mut Context) {
                                     button + 1,
                                     Some(remaining_sum - presses * buttons[button]),
                                     button_presses,
-                                ) && result < best_result
+                                )? && result < best_result
                                 {
                                     best_result = result;
                                 }
                             }
                             if best_result == u32::MAX {
-                                None
+                                Ok(None)
                             } else {
-                                Some(best_result)
+                                Ok(Some(best_result))
                             }
                         }
                     }
@@ -389,7 +398,8 @@ fn run(context: &mut Context) {
             }
         }
 
-        backtrack(
+        let calls_before = context.counter("backtrack_calls");
+        let presses = backtrack(
             context,
             machine,
             &matrix,
@@ -397,28 +407,55 @@ fn run(context: &mut Context) {
             0,
             None,
             vec![None; machine.buttons.len()],
-        )
-        .unwrap()
+        )?
+        .unwrap();
+        debug!(
+            context,
+            "machine {}: {} backtrack calls",
+            index + 1,
+            context.counter("backtrack_calls") - calls_before
+        );
+        Ok(presses)
     }
 
-    context.result(
-        machines
-            .iter()
-            .enumerate()
-            .map(|(index, machine)| solve_machine(context, index, machine))
-            .sum::<u32>(),
-    );
+    let mut total_presses = 0u32;
+    for (index, machine) in machines.iter().enumerate() {
+        context.progress(index, machines.len());
+        // Machines can take a while to solve, give up if the runner isn't waiting for us anymore
+        let Ok(presses) = solve_machine(context, index, machine) else {
+            return;
+        };
+        total_presses += presses;
+    }
+    context.result(total_presses);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Part::Both;
+    use crate::RunType::Full;
+    use std::io::sink;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+    use std::time::{Duration, Instant};
 
     #[test]
-    fn test_gcd() {
-        assert_eq!(gcd(12, 18), 6);
-        assert_eq!(gcd(12, 15), 3);
-        assert_eq!(gcd(4, 34), 2);
-        assert_eq!(gcd(12345, 1), 1);
+    fn stops_when_cancelled() {
+        // Takes minutes to solve with the back-tracking
+        let input = "[######] (4,5) (2,4) (0,4,5) (0,2,3,4) (1,3) (2,3,4,5) (1,5) (0,3,4,5) (0,1,2,5) (3,4,5) \
+                     {75,56,81,58,124,113}";
+        let mut output = sink();
+        let mut context = Context::new(input, 10, &INFO, Full, Both, &mut output);
+        let cancelled = context.cancelled.clone();
+        let start = Instant::now();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            cancelled.store(true, Ordering::Relaxed);
+        });
+        run(&mut context);
+        let elapsed = start.elapsed();
+        assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+        assert_eq!(context.results.len(), 1);
     }
 }
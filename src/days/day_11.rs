@@ -1,4 +1,5 @@
 use crate::RunType::Examples;
+use crate::parse::{ParseError, numbered_lines};
 use crate::{Context, DayInfo};
 use kust::ScopeFunctions;
 use std::collections::HashMap;
@@ -6,6 +7,7 @@ use std::collections::HashMap;
 pub const INFO: DayInfo = DayInfo {
     name: "Reactor",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 aaa: you hhh
 you: bbb ccc
@@ -17,9 +19,13 @@ fff: out
 ggg: out
 hhh: ccc fff iii
 iii: out",
+    generator: None,
+    validator: None,
+    streaming: false,
+    variants: &[],
 };
 
-const EXAMPLE2: &'static str = "\
+const EXAMPLE2: &str = "\
 svr: aaa bbb
 aaa: fft
 fft: ccc
@@ -34,21 +40,24 @@ fff: ggg hhh
 ggg: out
 hhh: out";
 
-fn parse(input: &str) -> HashMap<&str, Vec<&str>> {
+fn parse(input: &str) -> Result<HashMap<&str, Vec<&str>>, ParseError> {
     let mut adj = HashMap::new();
 
-    for line in input.lines() {
-        let mut parts = line.split(':');
-        let source = parts.next().unwrap();
-
-        let targets = parts.next().unwrap().trim().split_whitespace();
-        adj.insert(source, Vec::new());
-        let source_adj = adj.get_mut(source).unwrap();
-        for target in targets {
-            source_adj.push(target);
+    for (number, line) in numbered_lines(input) {
+        let (source, targets) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(number, "expected a device followed by ':'"))?;
+        if adj
+            .insert(source, targets.split_whitespace().collect())
+            .is_some()
+        {
+            return Err(ParseError::new(
+                number,
+                format!("duplicate device {source:?}"),
+            ));
         }
     }
-    adj
+    Ok(adj)
 }
 
 fn count_paths(adj: &HashMap<&str, Vec<&str>>, start: &str, end: &str, must_visit: &[&str]) -> u64 {
@@ -76,21 +85,17 @@ fn count_paths(adj: &HashMap<&str, Vec<&str>>, start: &str, end: &str, must_visi
             .iter()
             .map(|&node| bfs(adj, vis, node, target, &must_visit))
             .sum::<u64>()
-            .also(|&count| {
-                vis.entry(current)
-                    .or_insert_with(|| HashMap::new())
-                    .insert(must_visit, count)
-            })
+            .also(|&count| vis.entry(current).or_default().insert(must_visit, count))
     }
 
     bfs(adj, &mut HashMap::new(), start, end, &Vec::from(must_visit))
 }
 
 fn run(context: &mut Context) {
-    let mut adj = parse(context.input);
+    let mut adj = context.parse(parse);
     context.result(count_paths(&adj, "you", "out", &[]));
     if context.run_type == Examples {
-        adj = parse(EXAMPLE2);
+        adj = parse(EXAMPLE2).unwrap();
     }
     context.result(count_paths(&adj, "svr", "out", &["dac", "fft"]));
 }
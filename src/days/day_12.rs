@@ -1,71 +1,113 @@
 use crate::RunType::Examples;
-use crate::{Context, DayInfo};
+use crate::answer::Answer;
+use crate::generate::InputGenerator;
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::solution::Solution;
+use crate::utils::random::Rng;
+use crate::{Context, DayInfo, info};
 use regex::Regex;
-use std::iter::once;
+use std::collections::BTreeSet;
 use std::sync::LazyLock;
 
 pub const INFO: DayInfo = DayInfo {
     name: "Christmas Tree Farm",
     run,
+    parse: |input| parse(input).map(drop),
     example: "",
+    generator: Some(&Generator),
+    validator: None,
+    streaming: false,
+    variants: &[&Packing],
 };
 
 static AREA_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(\d+)x(\d+): (\d+(?: \d+)*)").unwrap());
+    LazyLock::new(|| Regex::new(r"^(\d+)x(\d+): (\d+(?: \d+)*)$").unwrap());
+
+/// Cells of a present, as (x, y) offsets from the top-left corner of its 3x3 square
+type Shape = Vec<(usize, usize)>;
+
+struct Region {
+    width: usize,
+    height: usize,
+    /// Number of presents of each shape
+    counts: Vec<usize>,
+}
+
+fn parse(input: &str) -> Result<(Vec<Shape>, Vec<Region>), ParseError> {
+    let mut lines = numbered_lines(input).peekable();
+    let mut shapes = Vec::new();
+    while let Some(&(number, header)) = lines.peek()
+        && header.ends_with(':')
+    {
+        lines.next();
+        if header != format!("{}:", shapes.len()) {
+            return Err(ParseError::new(
+                number,
+                format!("expected present {}", shapes.len()),
+            ));
+        }
+        let mut cells = Vec::new();
+        let rows = lines.by_ref().take_while(|(_, line)| !line.is_empty());
+        for (y, (number, line)) in rows.enumerate() {
+            for (x, char) in line.chars().enumerate() {
+                match char {
+                    '#' if x < 3 && y < 3 => cells.push((x, y)),
+                    '.' => {}
+                    '#' => return Err(ParseError::new(number, "present larger than 3x3")),
+                    _ => return Err(ParseError::new(number, format!("invalid cell {char:?}"))),
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err(ParseError::new(number, "empty present"));
+        }
+        shapes.push(cells);
+    }
+    let regions = lines
+        .map(|(number, line)| {
+            let m = AREA_REGEX
+                .captures(line)
+                .ok_or_else(|| ParseError::new(number, "invalid region"))?;
+            let counts = parse::numbers(number, &m[3], ' ')?;
+            if counts.len() != shapes.len() {
+                return Err(ParseError::new(number, "expected a count for each present"));
+            }
+            Ok(Region {
+                width: parse::number(number, &m[1])?,
+                height: parse::number(number, &m[2])?,
+                counts,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((shapes, regions))
+}
 
 fn run(context: &mut Context) {
     if context.run_type == Examples {
-        context.result("examples not supported for this day");
-        context.result("-");
+        info!(context, "examples not supported for this day");
+        context.result(Answer::NotApplicable);
+        context.result(Answer::NotApplicable);
         return;
     }
 
-    let (present_sizes, areas) = {
-        let mut lines = context.input.lines();
-        let mut present_sizes: Vec<u32> = Vec::new();
-        let first = loop {
-            let mut line = lines.next().unwrap();
-            if !line.ends_with(":") {
-                break line;
-            }
-            let mut area = 0;
-            loop {
-                area += line.chars().filter(|&c| c == '#').count() as u32;
-                line = lines.next().unwrap();
-                if line.is_empty() {
-                    break;
-                }
-            }
-            present_sizes.push(area);
-        };
-        let areas = once(first).chain(lines).map(|line| {
-            let m = AREA_REGEX.captures(line).unwrap();
-            let width = m[1].parse::<u32>().unwrap();
-            let height = m[2].parse::<u32>().unwrap();
-            let presents = m[3]
-                .split_whitespace()
-                .map(|s| s.parse::<u32>().unwrap())
-                .collect::<Vec<_>>();
-            (width * height, presents)
-        });
-        (present_sizes, areas)
-    };
+    let (shapes, regions) = context.parse(parse);
 
     let mut definitely_fit = 0u32;
-    #[allow(unused_variables)]
-    let mut definitely_dont_fit = 0u32;
     let mut not_sure = 0u32;
 
-    for (area, presents) in areas {
-        let min_area = presents
+    for (index, region) in regions.iter().enumerate() {
+        context.progress(index, regions.len());
+        let area = region.width * region.height;
+        let min_area = region
+            .counts
             .iter()
-            .enumerate()
-            .map(|(present, &count)| count * present_sizes[present])
-            .sum::<u32>();
-        let max_area = 9 * presents.iter().sum::<u32>();
+            .zip(&shapes)
+            .map(|(&count, shape)| count * shape.len())
+            .sum::<usize>();
+        let max_area = 9 * region.counts.iter().sum::<usize>();
 
         if area < min_area {
-            definitely_dont_fit += 1;
+            // definitely doesn't fit
         } else if area > max_area {
             definitely_fit += 1;
         } else {
@@ -77,5 +119,202 @@ fn run(context: &mut Context) {
     context.result(definitely_fit + not_sure);
 
     // no part 2
-    context.result("-");
+    context.result(Answer::NotApplicable);
+}
+
+/// Actually packs the presents into each region, trying every orientation and position
+struct Packing;
+
+impl Packing {
+    /// All distinct rotations and flips of a shape
+    fn orientations(shape: &Shape) -> Vec<Shape> {
+        let mut orientations = BTreeSet::new();
+        let mut cells = shape.clone();
+        for _ in 0..4 {
+            // Rotate by 90 degrees
+            cells = cells.iter().map(|&(x, y)| (y, 2 - x)).collect();
+            let flipped = cells.iter().map(|&(x, y)| (2 - x, y)).collect();
+            orientations.extend([Self::normalize(cells.clone()), Self::normalize(flipped)]);
+        }
+        orientations.into_iter().collect()
+    }
+
+    /// Moves the shape to the top-left corner, with its cells sorted
+    fn normalize(mut cells: Shape) -> Shape {
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap();
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap();
+        cells = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+        cells.sort();
+        cells
+    }
+
+    fn fits(region: &Region, shapes: &[Shape]) -> bool {
+        let cells = (region.counts.iter().zip(shapes))
+            .map(|(count, shape)| count * shape.len())
+            .sum::<usize>();
+        if cells > region.width * region.height {
+            return false;
+        }
+        let presents = (region.counts.iter().enumerate())
+            .flat_map(|(shape, &count)| vec![Self::orientations(&shapes[shape]); count])
+            .collect::<Vec<_>>();
+        let mut grid = vec![vec![false; region.width]; region.height];
+        Self::place(&mut grid, &presents)
+    }
+
+    /// Tries to place all the given presents (each given as its orientations) in the free cells of the grid
+    fn place(grid: &mut [Vec<bool>], presents: &[Vec<Shape>]) -> bool {
+        let Some((orientations, rest)) = presents.split_first() else {
+            return true;
+        };
+        let (width, height) = (grid[0].len(), grid.len());
+        for cells in orientations {
+            for top in 0..height {
+                for left in 0..width {
+                    let free = cells.iter().all(|&(x, y)| {
+                        grid.get(top + y)
+                            .and_then(|row| row.get(left + x))
+                            .is_some_and(|&taken| !taken)
+                    });
+                    if !free {
+                        continue;
+                    }
+                    cells
+                        .iter()
+                        .for_each(|&(x, y)| grid[top + y][left + x] = true);
+                    if Self::place(grid, rest) {
+                        return true;
+                    }
+                    cells
+                        .iter()
+                        .for_each(|&(x, y)| grid[top + y][left + x] = false);
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Solution for Packing {
+    type Input = (Vec<Shape>, Vec<Region>);
+
+    fn name(&self) -> &'static str {
+        "packing"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, (shapes, regions): &Self::Input, context: &Context) -> Answer {
+        if context.run_type == Examples {
+            return Answer::NotApplicable;
+        }
+        let fitting = regions.iter().filter(|region| Self::fits(region, shapes));
+        fitting.count().into()
+    }
+
+    fn part2(&self, _input: &Self::Input, _context: &Context) -> Answer {
+        Answer::NotApplicable
+    }
+}
+
+/// Four random 3x3 presents and `size` regions like in the real input: either too small for the presents'
+/// cells, large enough to give every present its own 3x3 square (up to `size` cells wider than that), or borderline:
+/// a small region where presents were packed at random, which is smaller than 9 cells per present but still fits them.
+struct Generator;
+
+impl Generator {
+    /// Counts of presents packed at random positions and orientations in a region, until it is dense enough that
+    /// the presents couldn't each get their own 3x3 square
+    fn pack(shapes: &[Shape], width: usize, height: usize, rng: &mut Rng) -> Vec<usize> {
+        let orientations = shapes.iter().map(Packing::orientations).collect::<Vec<_>>();
+        let mut grid = vec![vec![false; width]; height];
+        let mut counts = vec![0; shapes.len()];
+        for _ in 0..200 {
+            if 9 * counts.iter().sum::<usize>() > width * height {
+                break;
+            }
+            let shape = rng.below(shapes.len() as u64) as usize;
+            let cells = &orientations[shape][rng.below(orientations[shape].len() as u64) as usize];
+            let (left, top) = (
+                rng.below(width as u64) as usize,
+                rng.below(height as u64) as usize,
+            );
+            let free = cells.iter().all(|&(x, y)| {
+                grid.get(top + y)
+                    .and_then(|row| row.get(left + x))
+                    .is_some_and(|&taken| !taken)
+            });
+            if free {
+                cells
+                    .iter()
+                    .for_each(|&(x, y)| grid[top + y][left + x] = true);
+                counts[shape] += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        let mut shapes = Vec::<Shape>::new();
+        for index in 0..4 {
+            let mut cells = [false; 9];
+            let mut filled = 0;
+            let target = rng.range(5..=7);
+            while filled < target {
+                let cell = &mut cells[rng.below(9) as usize];
+                if !*cell {
+                    *cell = true;
+                    filled += 1;
+                }
+            }
+            shapes.push(
+                (0..9)
+                    .filter(|&cell| cells[cell])
+                    .map(|cell| (cell % 3, cell / 3))
+                    .collect(),
+            );
+            let rows = cells.chunks(3).map(|row| {
+                row.iter()
+                    .map(|&c| if c { '#' } else { '.' })
+                    .collect::<String>()
+            });
+            input += &format!("{index}:\n{}\n\n", rows.collect::<Vec<_>>().join("\n"));
+        }
+        let extra = size.min(20) as i64;
+        let regions = (0..size).map(|_| {
+            let mut width = rng.range(3..=3 + extra) as usize;
+            let mut height = rng.range(3..=3 + extra) as usize;
+            let mut counts = vec![0; shapes.len()];
+            match rng.below(3) {
+                0 => {
+                    // Every present gets its own 3x3 square
+                    for _ in 0..rng.range(0..=((width / 3) * (height / 3)) as i64) {
+                        counts[rng.below(shapes.len() as u64) as usize] += 1;
+                    }
+                }
+                1 => {
+                    // More cells than the region has
+                    let mut cells = 0;
+                    while cells <= width * height {
+                        let shape = rng.below(shapes.len() as u64) as usize;
+                        counts[shape] += 1;
+                        cells += shapes[shape].len();
+                    }
+                }
+                _ => {
+                    // Small enough for `Packing` to check quickly
+                    (width, height) = (rng.range(3..=5) as usize, rng.range(3..=4) as usize);
+                    counts = Self::pack(&shapes, width, height, rng);
+                }
+            }
+            let counts = counts.iter().map(usize::to_string).collect::<Vec<_>>();
+            format!("{width}x{height}: {}", counts.join(" "))
+        });
+        input + &regions.collect::<Vec<_>>().join("\n")
+    }
 }
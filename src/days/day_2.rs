@@ -1,14 +1,20 @@
-use crate::RunType::Examples;
-use crate::{Context, DayInfo, debug_example};
-use std::ops::Add;
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::validate::Rule;
+use crate::{Context, DayInfo, trace};
+use std::ops::{Add, RangeInclusive};
 
 pub const INFO: DayInfo = DayInfo {
     name: "Gift Shop",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
 824824821-824824827,2121212118-2121212124",
+    generator: None,
+    validator: Some(&[Rule::EachLine(check_ranges)]),
+    streaming: false,
+    variants: &[],
 };
 
 fn is_invalid_id_part1(id: u64) -> bool {
@@ -27,11 +33,11 @@ fn is_invalid_id_part2(id: u64) -> bool {
         return false;
     }
     'outer: for len in 1..=str.len() / 2 {
-        if str.len() % len != 0 {
+        if !str.len().is_multiple_of(len) {
             continue;
         }
         let (part, mut rest) = str.split_at(len);
-        while rest.len() > 0 {
+        while !rest.is_empty() {
             if !rest.starts_with(part) {
                 continue 'outer;
             }
@@ -42,45 +48,65 @@ fn is_invalid_id_part2(id: u64) -> bool {
     false
 }
 
-fn calc_sum<'a, R, F>(
-    #[allow(unused_variables)] context: &Context,
-    ids: R,
-    product_id_check: F,
-) -> u64
+fn calc_sum<R, F>(context: &Context, ids: R, product_id_check: F) -> u64
 where
     R: Iterator<Item = u64>,
     F: Fn(u64) -> bool,
 {
-    ids.filter_map(|product_id| {
+    ids.filter(|&product_id| {
         if product_id_check(product_id) {
-            debug_example!(context, "{product_id}");
-            Some(product_id)
+            trace!(context, "{product_id}");
+            true
         } else {
-            None
+            false
         }
     })
     .reduce(u64::add)
     .unwrap_or(0)
 }
 
-fn run(context: &mut Context) {
-    // Example splits the input across multiple lines, not present in full input
-    let input = if context.run_type == Examples {
-        &context
-            .input
-            .lines()
-            .fold(String::new(), |acc, line| acc + line)
+fn parse(input: &str) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    // The example splits the ranges across multiple lines, after a comma
+    let mut ranges = Vec::new();
+    for (number, line) in numbered_lines(input) {
+        for range in line.split(',').filter(|range| !range.is_empty()) {
+            let (from, to) = range
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(number, format!("invalid range {range:?}")))?;
+            let (from, to) = (parse::number(number, from)?, parse::number(number, to)?);
+            if from >= to {
+                return Err(ParseError::new(number, format!("empty range {range:?}")));
+            }
+            ranges.push(from..=to);
+        }
+    }
+    Ok(ranges)
+}
+
+/// Ranges must go from a lower to a higher id
+fn check_ranges(line: &str) -> Result<(), String> {
+    let reversed = line
+        .split(',')
+        .filter(|range| {
+            let ids = range
+                .split_once('-')
+                .and_then(|(from, to)| Some((from.parse::<u64>().ok()?, to.parse::<u64>().ok()?)));
+            ids.is_some_and(|(from, to)| from >= to)
+        })
+        .collect::<Vec<_>>();
+    if reversed.is_empty() {
+        Ok(())
     } else {
-        context.input
-    };
-    let ids = input.split(',').flat_map(|seq| {
-        let mut it = seq.split('-');
-        let from: u64 = it.next().unwrap().parse().unwrap();
-        let to: u64 = it.next().unwrap().parse().unwrap();
-        assert!(it.next().is_none());
-        assert!(from < to);
-        from..=to
-    });
+        Err(format!(
+            "ranges not in increasing order: {}",
+            reversed.join(", ")
+        ))
+    }
+}
+
+fn run(context: &mut Context) {
+    let ranges = context.parse(parse);
+    let ids = ranges.iter().flat_map(|range| range.clone());
     context.result(calc_sum(context, ids.clone(), is_invalid_id_part1));
     context.result(calc_sum(context, ids, is_invalid_id_part2));
 }
@@ -1,13 +1,24 @@
-use crate::{Context, DayInfo, debug_example};
+use crate::answer::Answer;
+use crate::generate::InputGenerator;
+use crate::parse::{ParseError, numbered_lines};
+use crate::solution::Solution;
+use crate::utils::random::Rng;
+use crate::{Context, DayInfo, trace};
+use std::collections::HashMap;
 
 pub const INFO: DayInfo = DayInfo {
     name: "Lobby",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 987654321111111
 811111111111119
 234234234234278
 818181911112111",
+    generator: Some(&Generator),
+    validator: None,
+    streaming: true,
+    variants: &[&Exhaustive],
 };
 
 type Bank = Vec<u8>;
@@ -43,30 +54,114 @@ fn max_joltage2(bank: Bank) -> u64 {
     joltage
 }
 
-fn calc_total_joltage<T, F>(
-    #[allow(unused_variables)] context: &Context,
-    banks: T,
-    get_max_joltage: F,
-) -> u64
-where
-    T: Iterator<Item = Bank>,
-    F: Fn(Bank) -> u64,
-{
-    banks
-        .map(|bank| {
-            let joltage = get_max_joltage(bank);
-            debug_example!(context, "{joltage}");
-            joltage
-        })
-        .sum()
+fn parse_bank(number: usize, line: &str) -> Result<Bank, ParseError> {
+    let bank = line
+        .chars()
+        .map(|char| char.to_digit(10).map(|digit| digit as u8))
+        .collect::<Option<Bank>>()
+        .ok_or_else(|| ParseError::new(number, "expected only digits"))?;
+    if bank.len() < 12 {
+        return Err(ParseError::new(number, "bank has fewer than 12 batteries"));
+    }
+    Ok(bank)
+}
+
+fn parse(input: &str) -> Result<Vec<Bank>, ParseError> {
+    numbered_lines(input)
+        .map(|(number, line)| parse_bank(number, line))
+        .collect()
 }
 
 fn run(context: &mut Context) {
-    let banks = context.input.lines().map(|line| {
-        line.chars()
-            .map(|char| char.to_digit(10).unwrap() as u8)
-            .collect::<Bank>()
-    });
-    context.result(calc_total_joltage(context, banks.clone(), max_joltage1));
-    context.result(calc_total_joltage(context, banks, max_joltage2));
+    // Streamed, both parts are computed in a single pass over the banks; part 1's time, memory and counters
+    // therefore cover both parts, and part 2's are next to nothing
+    let (mut total1, mut total2) = (0, 0);
+    for bank in context.parse_lines(parse_bank) {
+        let (joltage1, joltage2) = (max_joltage1(bank.clone()), max_joltage2(bank));
+        trace!(context, "{joltage1} {joltage2}");
+        total1 += joltage1;
+        total2 += joltage2;
+    }
+    context.result(total1);
+    context.result(total2);
+}
+
+/// Tries every way of picking the batteries, instead of picking the best digit greedily. The best picks after each
+/// battery are memoized, so real banks of about 100 batteries take ~100² steps rather than C(100, 12)
+struct Exhaustive;
+
+impl Exhaustive {
+    /// Largest number formed by `count` digits of `bank`, in order
+    fn max_joltage(bank: &[u8], count: usize) -> u64 {
+        Self::max_joltage_from(bank, 0, count, &mut HashMap::new())
+    }
+
+    /// Largest number formed by `count` digits of `bank` from index `start` on, memoized on `(start, count)`
+    fn max_joltage_from(
+        bank: &[u8],
+        start: usize,
+        count: usize,
+        memo: &mut HashMap<(usize, usize), u64>,
+    ) -> u64 {
+        if count == 0 {
+            return 0;
+        }
+        if let Some(&joltage) = memo.get(&(start, count)) {
+            return joltage;
+        }
+        let joltage = (start..=bank.len() - count)
+            .map(|first| {
+                let rest = Self::max_joltage_from(bank, first + 1, count - 1, memo);
+                bank[first] as u64 * 10u64.pow(count as u32 - 1) + rest
+            })
+            .max()
+            .unwrap();
+        memo.insert((start, count), joltage);
+        joltage
+    }
+}
+
+impl Solution for Exhaustive {
+    type Input = Vec<Bank>;
+
+    fn name(&self) -> &'static str {
+        "exhaustive"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Bank>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, banks: &Vec<Bank>, _context: &Context) -> Answer {
+        banks
+            .iter()
+            .map(|bank| Self::max_joltage(bank, 2))
+            .sum::<u64>()
+            .into()
+    }
+
+    fn part2(&self, banks: &Vec<Bank>, _context: &Context) -> Answer {
+        banks
+            .iter()
+            .map(|bank| Self::max_joltage(bank, 12))
+            .sum::<u64>()
+            .into()
+    }
+}
+
+/// `size` banks of random digits; banks have 12 digits plus up to `size` more, at most 100 in total
+struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let length = rng.range(12..=12 + size.min(88) as i64);
+                (0..length)
+                    .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
@@ -1,9 +1,13 @@
 use crate::days::day_4::Tile::{Empty, PaperRoll};
-use crate::{debug_example, Context, DayInfo};
+use crate::parse::{ParseError, numbered_lines};
+use crate::validate::Rule;
+use crate::viz::Frame;
+use crate::{trace, Context, DayInfo};
 
 pub const INFO: DayInfo = DayInfo {
     name: "Printing Department",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 ..@@.@@@@.
 @@@.@.@.@@
@@ -15,6 +19,10 @@ pub const INFO: DayInfo = DayInfo {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@.",
+    generator: None,
+    validator: Some(&[Rule::SquareGrid]),
+    streaming: false,
+    variants: &[],
 };
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -23,17 +31,28 @@ enum Tile {
     Empty,
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Empty),
+            '@' => Ok(PaperRoll),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
         match value {
-            '.' => Empty,
-            '@' => PaperRoll,
-            _ => unreachable!(),
+            Empty => '.',
+            PaperRoll => '@',
         }
     }
 }
 
-fn is_accessible_roll(map: &Vec<Vec<Tile>>, roll_x: usize, roll_y: usize) -> bool {
+fn is_accessible_roll(map: &[Vec<Tile>], roll_x: usize, roll_y: usize) -> bool {
     if map[roll_y][roll_x] != PaperRoll {
         return false;
     }
@@ -60,19 +79,35 @@ fn is_accessible_roll(map: &Vec<Vec<Tile>>, roll_x: usize, roll_y: usize) -> boo
     nearby_rolls < 4
 }
 
+fn parse(input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    let map = numbered_lines(input)
+        .map(|(number, line)| {
+            line.chars()
+                .map(|char| {
+                    Tile::try_from(char)
+                        .map_err(|char| ParseError::new(number, format!("invalid tile {char:?}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if map.first().is_none_or(Vec::is_empty) {
+        return Err(ParseError::new(1, "empty map"));
+    }
+    if let Some(row) = map.iter().position(|row| row.len() != map[0].len()) {
+        return Err(ParseError::new(row + 1, "rows have different lengths"));
+    }
+    Ok(map)
+}
+
 fn run(context: &mut Context) {
-    let mut map = context
-        .input
-        .lines()
-        .map(|line| line.chars().map(Tile::from).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let mut map = context.parse(parse);
 
     // Count accessible tiles (part 1)
     let mut accessible_count: u32 = 0;
     for y in 0..map[0].len() {
         for x in 0..map.len() {
             if is_accessible_roll(&map, x, y) {
-                debug_example!(context, "accessible roll at x={x}, y={y}");
+                trace!(context, "accessible roll at x={x}, y={y}");
                 accessible_count += 1;
             }
         }
@@ -84,6 +119,7 @@ fn run(context: &mut Context) {
     let mut total_removed_rolls: u32 = 0;
     loop {
         let mut removed_rolls: u32 = 0;
+        let mut wave = Vec::new();
 
         for y in 0..map[0].len() {
             for x in 0..map.len() {
@@ -91,6 +127,7 @@ fn run(context: &mut Context) {
                     map[y][x] = Empty;
                     removed_rolls += 1;
                     total_removed_rolls += 1;
+                    wave.push((x, y));
                 }
             }
         }
@@ -98,6 +135,18 @@ fn run(context: &mut Context) {
         if removed_rolls == 0 {
             break;
         }
+
+        // Show the remaining rolls, marking the ones removed in this wave with 'x'
+        context.visualize("removal-wave", || {
+            let mut grid = map
+                .iter()
+                .map(|row| row.iter().map(|&tile| char::from(tile)).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            for &(x, y) in &wave {
+                grid[y][x] = 'x';
+            }
+            Frame::grid(grid)
+        });
     }
     context.result(total_removed_rolls);
 }
@@ -1,10 +1,14 @@
-use crate::{debug_example, Context, DayInfo};
+use crate::generate::InputGenerator;
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::utils::random::Rng;
+use crate::{Context, DayInfo, debug, trace};
 use std::cmp::{max, min};
 use std::ops::RangeInclusive;
 
 pub const INFO: DayInfo = DayInfo {
     name: "Cafeteria",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 3-5
 10-14
@@ -17,31 +21,63 @@ pub const INFO: DayInfo = DayInfo {
 11
 17
 32",
+    generator: Some(&Generator),
+    validator: None,
+    streaming: true,
+    variants: &[],
 };
 
-fn run(context: &mut Context) {
-    let mut lines = context.input.lines();
-    let ranges = {
-        let mut ranges = Vec::new();
-        loop {
-            let line = lines.next().unwrap();
-            if line.is_empty() {
-                break;
-            }
-            let mut nums = line.split('-');
-            let from: u64 = nums.next().unwrap().parse().unwrap();
-            let to: u64 = nums.next().unwrap().parse().unwrap();
-            assert!(nums.next().is_none());
-            assert!(to >= from);
-            ranges.push(from..=to);
+/// Fresh ingredient id ranges, up to the blank line that follows them
+fn parse_ranges(
+    lines: &mut impl Iterator<Item = (usize, impl AsRef<str>)>,
+) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    let mut ranges = Vec::new();
+    let mut last_number = 0;
+    loop {
+        let Some((number, line)) = lines.next() else {
+            return Err(ParseError::new(
+                last_number + 1,
+                "missing blank line after the ranges",
+            ));
+        };
+        let line = line.as_ref();
+        last_number = number;
+        if line.is_empty() {
+            break;
         }
-        ranges
-    };
-    let ids = lines.map(|line| line.parse::<u64>().unwrap());
-    let fresh = ids
+        let (from, to) = line
+            .split_once('-')
+            .ok_or_else(|| ParseError::new(number, "expected a range"))?;
+        let (from, to) = (parse::number(number, from)?, parse::number(number, to)?);
+        if to < from {
+            return Err(ParseError::new(number, "empty range"));
+        }
+        ranges.push(from..=to);
+    }
+    Ok(ranges)
+}
+
+/// Fresh ingredient id ranges, and the available ingredient ids
+fn parse(input: &str) -> Result<(Vec<RangeInclusive<u64>>, Vec<u64>), ParseError> {
+    let mut lines = numbered_lines(input);
+    let ranges = parse_ranges(&mut lines)?;
+    let ids = lines
+        .map(|(number, line)| parse::number(number, line))
+        .collect::<Result<_, _>>()?;
+    Ok((ranges, ids))
+}
+
+fn run(context: &mut Context) {
+    // Streamed: the ranges are kept, the ids are only checked against them as they are read
+    let mut lines = context.lines();
+    let ranges = parse_ranges(&mut lines).unwrap_or_else(|err| panic!("invalid input: {err}"));
+    let fresh = lines
+        .map(|(number, line)| {
+            parse::number::<u64>(number, &line).unwrap_or_else(|err| panic!("invalid input: {err}"))
+        })
         .filter(|id| {
             if ranges.iter().any(|range| range.contains(id)) {
-                debug_example!(context, "{id}");
+                trace!(context, "{id}");
                 true
             } else {
                 false
@@ -71,7 +107,7 @@ fn run(context: &mut Context) {
     }
 
     // Count all elements from merged ranges
-    debug_example!(context, "{merged_ranges:?}");
+    debug!(context, "merged ranges: {merged_ranges:?}");
     context.result(
         merged_ranges
             .iter()
@@ -79,3 +115,20 @@ fn run(context: &mut Context) {
             .sum::<u64>(),
     );
 }
+
+/// Random, possibly overlapping ranges and ids; `size` is the number of ranges, and of ids
+struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        // Keep the density of ranges the same for every size, so roughly the same fraction of ids is fresh
+        let max = size.max(1) as i64 * 1000;
+        let ranges = (0..size).map(|_| {
+            let from = rng.range(1..=max);
+            format!("{from}-{}", from + rng.range(0..=2000))
+        });
+        let ranges = ranges.collect::<Vec<_>>().join("\n");
+        let ids = (0..size).map(|_| rng.range(1..=max + 2000).to_string());
+        format!("{ranges}\n\n{}", ids.collect::<Vec<_>>().join("\n"))
+    }
+}
@@ -1,13 +1,19 @@
-use crate::{debug_example, Context, DayInfo};
+use crate::parse::{self, ParseError, numbered_lines};
+use crate::{trace, Context, DayInfo};
 
 pub const INFO: DayInfo = DayInfo {
     name: "Trash Compactor",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 123 328  51 64
  45 64  387 23
   6 98  215 314
 *   +   *   +  ",
+    generator: None,
+    validator: None,
+    streaming: false,
+    variants: &[],
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -32,40 +38,74 @@ impl Operator {
     }
 }
 
-impl From<char> for Operator {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Operator {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '+' => Operator::Addition,
-            '*' => Operator::Multiplication,
-            _ => panic!("Unknown operator: {value}"),
+            '+' => Ok(Operator::Addition),
+            '*' => Ok(Operator::Multiplication),
+            _ => Err(value),
+        }
+    }
+}
+
+struct Worksheet<'a> {
+    operators: Vec<Operator>,
+    /// Numbers of each line, read left to right (part 1)
+    numbers: Vec<Vec<u64>>,
+    /// Lines with the numbers, to be read column by column (part 2)
+    number_lines: Vec<&'a str>,
+    operator_line: &'a str,
+}
+
+fn parse(input: &str) -> Result<Worksheet<'_>, ParseError> {
+    let mut lines = numbered_lines(input).collect::<Vec<_>>();
+    let Some((operator_number, operator_line)) = lines.pop() else {
+        return Err(ParseError::new(1, "empty worksheet"));
+    };
+    let operators = operator_line
+        .split_whitespace()
+        .map(|operator| match operator.chars().collect::<Vec<_>>()[..] {
+            [operator] => Operator::try_from(operator).ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| ParseError::new(operator_number, "expected only + and * operators"))?;
+    let mut numbers = Vec::new();
+    for &(number, line) in &lines {
+        if line.contains(|char: char| !char.is_ascii_digit() && char != ' ') {
+            return Err(ParseError::new(number, "expected only digits and spaces"));
+        }
+        let row = line
+            .split_whitespace()
+            .map(|text| parse::number(number, text))
+            .collect::<Result<Vec<u64>, _>>()?;
+        if row.len() != operators.len() {
+            return Err(ParseError::new(
+                number,
+                format!("expected {} numbers, one per operator", operators.len()),
+            ));
         }
+        numbers.push(row);
     }
+    Ok(Worksheet {
+        operators,
+        numbers,
+        number_lines: lines.into_iter().map(|(_, line)| line).collect(),
+        operator_line,
+    })
 }
 
 fn run(context: &mut Context) {
+    let Worksheet {
+        operators,
+        numbers,
+        number_lines,
+        operator_line,
+    } = context.parse(parse);
+
     // Part 1
-    let (operators, numbers) = {
-        let mut numbers = context
-            .input
-            .lines()
-            .map(|line| line.split_whitespace().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let operators = numbers.pop().unwrap();
-        (
-            operators
-                .into_iter()
-                .map(|op| op.chars().next().unwrap().into())
-                .collect::<Vec<Operator>>(),
-            numbers
-                .into_iter()
-                .map(|line| {
-                    line.into_iter()
-                        .map(|s| s.parse().unwrap())
-                        .collect::<Vec<u64>>()
-                })
-                .collect::<Vec<_>>(),
-        )
-    };
     context.result(
         operators
             .iter()
@@ -74,26 +114,23 @@ fn run(context: &mut Context) {
                 let result = numbers.iter().fold(operator.identity(), |acc, line| {
                     operator.run(acc, line[index])
                 });
-                debug_example!(context, "col {index}: {result}");
+                trace!(context, "col {index}: {result}");
                 result
             })
             .sum::<u64>(),
     );
 
     // Part 2
-    let (operators, number_lines) = {
-        let mut lines = context
-            .input
-            .lines()
-            .map(|line| line.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        (lines.pop().unwrap(), lines)
-    };
+    let operators = operator_line.chars().collect::<Vec<_>>();
+    let number_lines = number_lines
+        .iter()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
     let max_cols = number_lines.iter().map(Vec::len).max().unwrap();
     let mut col = 0usize;
     let mut sum = 0u64;
     while col < max_cols {
-        let operator: Operator = operators[col].into();
+        let operator = Operator::try_from(operators[col]).unwrap();
         let mut acc = operator.identity();
         while number_lines
             .iter()
@@ -108,7 +145,7 @@ fn run(context: &mut Context) {
             acc = operator.run(acc, num);
             col += 1;
         }
-        debug_example!(context, "col {col}: {acc}");
+        trace!(context, "col {col}: {acc}");
         sum += acc;
         col += 1;
     }
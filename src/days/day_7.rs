@@ -1,9 +1,17 @@
+use crate::answer::Answer;
 use crate::days::day_7::Tile::{Laser, Splitter, Start};
+use crate::generate::InputGenerator;
+use crate::parse::{ParseError, numbered_lines};
+use crate::solution::Solution;
+use crate::utils::random::Rng;
+use crate::viz::Frame;
 use crate::{Context, DayInfo};
+use std::collections::BTreeSet;
 
 pub const INFO: DayInfo = DayInfo {
     name: "Laboratories",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 .......S.......
 ...............
@@ -21,6 +29,10 @@ pub const INFO: DayInfo = DayInfo {
 ...............
 .^.^.^.^.^...^.
 ...............",
+    generator: Some(&Generator),
+    validator: None,
+    streaming: false,
+    variants: &[&EveryTimeline],
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,32 +56,51 @@ impl TryFrom<char> for Tile {
     }
 }
 
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        match value {
+            Start => 'S',
+            Tile::Empty => '.',
+            Splitter(_) => '^',
+            Laser => '|',
+        }
+    }
+}
+
 type Map = Vec<Vec<Tile>>;
 
-fn run(context: &mut Context) {
-    let map: Map = context
-        .input
-        .lines()
-        .map(|line| {
+/// The manifold, and the position of the start
+fn parse(input: &str) -> Result<(Map, (usize, usize)), ParseError> {
+    let map: Map = numbered_lines(input)
+        .map(|(number, line)| {
             line.chars()
-                .map(|char| Tile::try_from(char).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let (start_x, start_y) = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            match line.iter().enumerate().find_map(|(x, &tile)| match tile {
-                Start => Some(x),
-                _ => None,
-            }) {
-                Some(x) => Some((x, y)),
-                None => None,
-            }
+                .map(|char| {
+                    Tile::try_from(char)
+                        .map_err(|char| ParseError::new(number, format!("invalid tile {char:?}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
         })
-        .unwrap();
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(row) = map.iter().position(|row| row.len() != map[0].len()) {
+        return Err(ParseError::new(row + 1, "rows have different lengths"));
+    }
+    let mut starts = map.iter().enumerate().flat_map(|(y, line)| {
+        line.iter()
+            .enumerate()
+            .filter(|&(_, &tile)| tile == Start)
+            .map(move |(x, _)| (x, y))
+    });
+    let start = starts
+        .next()
+        .ok_or_else(|| ParseError::new(1, "no start"))?;
+    if let Some((_, y)) = starts.next() {
+        return Err(ParseError::new(y + 1, "more than one start"));
+    }
+    Ok((map, start))
+}
+
+fn run(context: &mut Context) {
+    let (map, (start_x, start_y)) = context.parse(parse);
 
     fn simulate_laser(map: &mut Map, start_x: usize, start_y: usize) -> u32 {
         let mut splits = 0u32;
@@ -121,6 +152,94 @@ fn run(context: &mut Context) {
         1
     }
 
-    context.result(simulate_laser(&mut map.clone(), start_x, start_y));
+    let mut laser_map = map.clone();
+    let splits = simulate_laser(&mut laser_map, start_x, start_y);
+    context.visualize("beams", || {
+        Frame::grid(
+            laser_map
+                .iter()
+                .map(|line| line.iter().map(|&tile| char::from(tile))),
+        )
+    });
+    context.result(splits);
     context.result(simulate_quantum_laser(&mut map.clone(), start_x, start_y));
 }
+
+/// Moves all beams down one row at a time, and follows every timeline separately instead of memoizing them
+struct EveryTimeline;
+
+impl EveryTimeline {
+    /// Number of timelines of a particle entering the manifold at the given position
+    fn timelines(map: &Map, x: usize, y: usize) -> u64 {
+        match map.get(y) {
+            None => 1,
+            Some(row) if matches!(row[x], Splitter(_)) => {
+                Self::timelines(map, x - 1, y + 1) + Self::timelines(map, x + 1, y + 1)
+            }
+            Some(_) => Self::timelines(map, x, y + 1),
+        }
+    }
+}
+
+impl Solution for EveryTimeline {
+    type Input = (Map, (usize, usize));
+
+    fn name(&self) -> &'static str {
+        "every-timeline"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, (map, (x, y)): &Self::Input, _context: &Context) -> Answer {
+        let mut beams = BTreeSet::from([*x]);
+        let mut splits = 0u32;
+        for row in &map[y + 1..] {
+            let mut next = BTreeSet::new();
+            for &x in &beams {
+                if matches!(row[x], Splitter(_)) {
+                    splits += 1;
+                    next.extend([x - 1, x + 1]);
+                } else {
+                    next.insert(x);
+                }
+            }
+            beams = next;
+        }
+        splits.into()
+    }
+
+    fn part2(&self, (map, (x, y)): &Self::Input, _context: &Context) -> Answer {
+        Self::timelines(map, *x, y + 1).into()
+    }
+}
+
+/// A manifold like the example: `size` rows of splitters, each followed by an empty row.
+/// Splitters are never next to each other nor on the edges, so beams never leave the manifold.
+struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let width = 2 * size + 3;
+        let start = width / 2;
+        let mut rows = vec![format!("{}S{}", ".".repeat(start), ".".repeat(start))];
+        for row in 0..size {
+            let splitters = (0..width)
+                .map(|x| {
+                    let inside = x > 0 && x < width - 1;
+                    // Alternate the columns of each row, like the beams do
+                    let column = x % 2 == (start + row) % 2;
+                    if inside && column && rng.chance(0.6) {
+                        '^'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            rows.push(splitters);
+            rows.push(".".repeat(width));
+        }
+        rows.join("\n")
+    }
+}
@@ -1,9 +1,10 @@
 use crate::Part::One;
 use crate::RunType::Examples;
-use crate::{Context, DayInfo, debug_example};
+use crate::parse::{ParseError, numbered_lines};
+use crate::utils::union_find::DisjointSet;
+use crate::{Context, DayInfo, debug, trace};
 use PointParseError::*;
 use kust::ScopeFunctions;
-use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::num::ParseIntError;
@@ -13,6 +14,7 @@ use thiserror::Error;
 pub const INFO: DayInfo = DayInfo {
     name: "Playground",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 162,817,812
 57,618,57
@@ -34,6 +36,10 @@ pub const INFO: DayInfo = DayInfo {
 862,61,35
 984,92,344
 425,690,689",
+    generator: None,
+    validator: None,
+    streaming: false,
+    variants: &[],
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -123,115 +129,68 @@ impl Hash for Segment {
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    numbered_lines(input)
+        .map(|(number, line)| Point::from_str(line).map_err(|err| ParseError::new(number, err)))
+        .collect()
+}
+
 fn run(context: &mut Context) {
-    let points = context
-        .input
-        .lines()
-        .map(Point::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|err| err.to_string())
-        .unwrap();
-    let initial_connections_count = if context.run_type == Examples {
-        10
-    } else {
-        1000
-    };
+    let points = context.parse(parse);
+    let initial_connections_count = context.param(
+        "connections",
+        if context.run_type == Examples {
+            10
+        } else {
+            1000
+        },
+    );
 
-    // All possible segments, sorted by their length
-    let segments = points
-        .iter()
-        .enumerate()
-        .flat_map(|(index, &p1)| {
-            points[index + 1..].iter().map(move |&p2| {
-                let segment = Segment(p1, p2);
-                let length = segment.length_sq();
-                (segment, length)
-            })
+    // All possible segments (as pairs of point indices), sorted by their length
+    let segments = (0..points.len())
+        .flat_map(|index1| (index1 + 1..points.len()).map(move |index2| (index1, index2)))
+        .map(|(index1, index2)| {
+            let length = Segment(points[index1], points[index2]).length_sq();
+            (index1, index2, length)
         })
         .collect::<Vec<_>>()
-        .apply(|v| v.sort_by_key(|(_, length)| *length));
+        .apply(|v| v.sort_by_key(|&(_, _, length)| length));
 
     // Connect segments, forming circuits
-    // For each point, remember the circuit ID
-    let mut next_circuit = 0u32;
-    let mut circuit_count = points.len();
-    let mut point_to_circuit: HashMap<Point, u32> = HashMap::new();
+    let mut circuits = DisjointSet::new(points.len());
 
     fn process_segment(
-        #[allow(unused_variables)] context: &Context,
-        segment: &Segment,
-        point_to_circuit: &mut HashMap<Point, u32>,
-        next_circuit: &mut u32,
-        circuit_count: &mut usize,
+        context: &Context,
+        points: &[Point],
+        circuits: &mut DisjointSet,
+        index1: usize,
+        index2: usize,
     ) {
-        match (
-            point_to_circuit.get(&segment.0),
-            point_to_circuit.get(&segment.1),
-        ) {
-            (Some(&circuit), None) => {
-                point_to_circuit.insert(segment.1, circuit);
-                debug_example!(context, "connecting {segment}; left was circuit {circuit}");
-                *circuit_count -= 1;
-            }
-            (None, Some(&circuit)) => {
-                point_to_circuit.insert(segment.0, circuit);
-                debug_example!(context, "connecting {segment}; right was circuit {circuit}");
-                *circuit_count -= 1;
-            }
-            (None, None) => {
-                point_to_circuit.insert(segment.0, *next_circuit);
-                point_to_circuit.insert(segment.1, *next_circuit);
-                debug_example!(
-                    context,
-                    "connecting {segment}; new circuit {}",
-                    *next_circuit
-                );
-                *next_circuit += 1;
-                *circuit_count -= 1;
-            }
-            (Some(&circuit1), Some(&circuit2)) => {
-                if circuit1 != circuit2 {
-                    debug_example!(
-                        context,
-                        "connecting {segment}; found circuits {circuit1} and {circuit2} (merging into {circuit1})"
-                    );
-                    for circuit in point_to_circuit.values_mut() {
-                        if *circuit == circuit2 {
-                            *circuit = circuit1;
-                        }
-                    }
-                    *circuit_count -= 1;
-                } else {
-                    debug_example!(context, "already connected {segment} on circuit {circuit1}");
-                }
-            }
-        };
+        let segment = Segment(points[index1], points[index2]);
+        if circuits.union(index1, index2) {
+            trace!(
+                context,
+                "connecting {segment}; circuit now has {} points",
+                circuits.component_size(index1)
+            );
+        } else {
+            trace!(context, "already connected {segment}");
+        }
     }
 
-    for (segment, _) in &segments[..initial_connections_count] {
-        process_segment(
-            context,
-            segment,
-            &mut point_to_circuit,
-            &mut next_circuit,
-            &mut circuit_count,
-        );
+    for &(index1, index2, _) in &segments[..initial_connections_count] {
+        process_segment(context, &points, &mut circuits, index1, index2);
     }
 
-    let circuit_sizes = {
-        let mut sizes = vec![0u32; next_circuit as usize];
-        for &circuit in point_to_circuit.values() {
-            sizes[circuit as usize] += 1
-        }
+    let circuit_sizes = circuits.component_sizes().apply(|sizes| {
         sizes.sort();
         sizes.reverse();
-        sizes
-    };
+    });
     context.result(
         circuit_sizes
             .iter()
             .take(3)
-            .map(|n| *n as u64)
+            .map(|&n| n as u64)
             .product::<u64>(),
     );
 
@@ -239,17 +198,15 @@ fn run(context: &mut Context) {
         return;
     }
 
-    for (segment, _) in &segments[initial_connections_count..] {
-        process_segment(
-            context,
-            segment,
-            &mut point_to_circuit,
-            &mut next_circuit,
-            &mut circuit_count,
-        );
-        if circuit_count == 1 {
-            debug_example!(context, "connected all circuits at {segment}");
-            context.result(segment.0.x * segment.1.x);
+    for &(index1, index2, _) in &segments[initial_connections_count..] {
+        process_segment(context, &points, &mut circuits, index1, index2);
+        if circuits.component_count() == 1 {
+            debug!(
+                context,
+                "connected all circuits at {}",
+                Segment(points[index1], points[index2])
+            );
+            context.result(points[index1].x * points[index2].x);
             break;
         }
     }
@@ -272,6 +229,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::manual_hash_one)]
         fn hash_commutative() {
             let state = RandomState::new();
             let mut h1 = state.build_hasher();
@@ -1,5 +1,10 @@
 use crate::Part::One;
-use crate::{Context, DayInfo, debug_example};
+use crate::generate::InputGenerator;
+use crate::parse::{ParseError, numbered_lines};
+use crate::utils::random::Rng;
+use crate::validate::Rule;
+use crate::viz::{Frame, Svg};
+use crate::{Context, DayInfo, trace};
 use Turn::*;
 use displaythis::Display;
 use std::cmp::{max, min};
@@ -12,6 +17,7 @@ use thiserror::Error;
 pub const INFO: DayInfo = DayInfo {
     name: "Movie Theater",
     run,
+    parse: |input| parse(input).map(drop),
     example: "\
 7,1
 11,1
@@ -21,6 +27,10 @@ pub const INFO: DayInfo = DayInfo {
 2,5
 2,3
 7,3",
+    generator: Some(&Generator),
+    validator: Some(&[Rule::Input(check_loop)]),
+    streaming: false,
+    variants: &[],
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Display)]
@@ -123,10 +133,12 @@ fn calc_turn(p1: Point, p2: Point, p3: Point) -> Turn {
     }
 }
 
-fn identify_largest_rect_area(
-    points: &Vec<Point>,
+/// Finds the largest valid rectangle with opposite corners on two of the points.
+/// Returns its area, and its top-left and bottom-right corners.
+fn identify_largest_rect(
+    points: &[Point],
     validate_rect: impl Fn(Point, Point) -> bool,
-) -> u64 {
+) -> (u64, Point, Point) {
     points
         .iter()
         .enumerate()
@@ -134,29 +146,62 @@ fn identify_largest_rect_area(
             points[index + 1..]
                 .iter()
                 .filter_map(|p2| {
-                    if !validate_rect(
-                        Point {
-                            x: min(p1.x, p2.x),
-                            y: min(p1.y, p2.y),
-                        },
-                        Point {
-                            x: max(p1.x, p2.x),
-                            y: max(p1.y, p2.y),
-                        },
-                    ) {
+                    let top_left = Point {
+                        x: min(p1.x, p2.x),
+                        y: min(p1.y, p2.y),
+                    };
+                    let bottom_right = Point {
+                        x: max(p1.x, p2.x),
+                        y: max(p1.y, p2.y),
+                    };
+                    if !validate_rect(top_left, bottom_right) {
                         None
                     } else {
                         let width = p1.x.abs_diff(p2.x) + 1;
                         let height = p1.y.abs_diff(p2.y) + 1;
-                        Some((width as u64) * (height as u64))
+                        Some(((width as u64) * (height as u64), top_left, bottom_right))
                     }
                 })
-                .max()
+                .max_by_key(|&(area, _, _)| area)
         })
-        .max()
+        .max_by_key(|&(area, _, _)| area)
         .unwrap()
 }
 
+/// Draws the polygon, with the chosen rectangle on top
+fn render_rect(points: &[Point], top_left: Point, bottom_right: Point) -> Frame {
+    let min_x = points.iter().map(|p| p.x).min().unwrap() as f64;
+    let max_x = points.iter().map(|p| p.x).max().unwrap() as f64;
+    let min_y = points.iter().map(|p| p.y).min().unwrap() as f64;
+    let max_y = points.iter().map(|p| p.y).max().unwrap() as f64;
+    // Leave a margin around the polygon
+    let margin = ((max_x - min_x).max(max_y - min_y) * 0.05).max(1.0);
+    let mut svg = Svg::new(
+        min_x - margin,
+        min_y - margin,
+        max_x - min_x + 2.0 * margin,
+        max_y - min_y + 2.0 * margin,
+    );
+    svg.polygon(
+        &points
+            .iter()
+            .map(|p| (p.x as f64, p.y as f64))
+            .collect::<Vec<_>>(),
+        "#2e7d32",
+        "#1b5e20",
+    )
+    .rect(
+        (top_left.x as f64, top_left.y as f64),
+        (
+            (bottom_right.x - top_left.x) as f64,
+            (bottom_right.y - top_left.y) as f64,
+        ),
+        "rgba(255, 82, 82, 0.5)",
+        "#d50000",
+    );
+    Frame::Svg(svg)
+}
+
 fn calc_outside_segment_diff(p1: Point, p2: Point, loop_direction: Turn) -> Point {
     if p1.x == p2.x {
         if (p2.y > p1.y && loop_direction == Clockwise)
@@ -187,14 +232,61 @@ fn calc_outside_segment_diff(p1: Point, p2: Point, loop_direction: Turn) -> Poin
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    numbered_lines(input)
+        .map(|(number, line)| Point::from_str(line).map_err(|err| ParseError::new(number, err)))
+        .collect()
+}
+
+/// Corners must form a loop turning by 90 degrees at each corner, and a full turn in total, i.e. it doesn't spiral
+fn check_loop(input: &str) -> Vec<ParseError> {
+    // Malformed lines are reported by the parser
+    let Ok(points) = parse(input) else {
+        return Vec::new();
+    };
+    let n = points.len();
+    if n < 4 {
+        return vec![ParseError::new(1, "a loop needs at least 4 corners")];
+    }
+    let horizontal = |p1: Point, p2: Point| p1.y == p2.y && p1.x != p2.x;
+    let vertical = |p1: Point, p2: Point| p1.x == p2.x && p1.y != p2.y;
+    let violations = (0..n)
+        .filter(|&index| {
+            let (p1, p2, p3) = (
+                points[index],
+                points[(index + 1) % n],
+                points[(index + 2) % n],
+            );
+            !(horizontal(p1, p2) && vertical(p2, p3) || vertical(p1, p2) && horizontal(p2, p3))
+        })
+        .map(|index| ParseError::new((index + 1) % n + 1, "corner does not turn by 90 degrees"))
+        .collect::<Vec<_>>();
+    if !violations.is_empty() {
+        return violations;
+    }
+    let turn_count = (0..n)
+        .map(|index| {
+            i32::from(calc_turn(
+                points[index],
+                points[(index + 1) % n],
+                points[(index + 2) % n],
+            ))
+        })
+        .sum::<i32>();
+    if turn_count.abs() != 4 {
+        let message = format!("the loop turns {turn_count} times in total, instead of 4 or -4");
+        return vec![ParseError::new(1, message)];
+    }
+    Vec::new()
+}
+
 fn run(context: &mut Context) {
-    let points = context
-        .input
-        .lines()
-        .map(Point::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    context.result(identify_largest_rect_area(&points, |_, _| true));
+    let points = context.parse(parse);
+    let (area, top_left, bottom_right) = identify_largest_rect(&points, |_, _| true);
+    context.visualize("part1-rect", || {
+        render_rect(&points, top_left, bottom_right)
+    });
+    context.result(area);
     let n = points.len();
     if context.part == One {
         return;
@@ -210,7 +302,6 @@ fn run(context: &mut Context) {
             ))
         })
         .sum::<i32>();
-    assert!(turn_count == 4 || turn_count == -4);
 
     // If the loop is clockwise, the count will be 4 more clockwise turns than counter-clockwise.
     // If the loop is counter-clockwise, the count will be 4 more counter-clockwise turns.
@@ -220,6 +311,7 @@ fn run(context: &mut Context) {
     let mut outside_points: HashSet<Point> = HashSet::new();
     for index in 0..n {
         // For each segment
+        context.count("segments_processed");
         let mut p1 = points[index % n];
         let mut p2 = points[(index + 1) % n];
         let diff = calc_outside_segment_diff(p1, p2, loop_direction);
@@ -235,15 +327,12 @@ fn run(context: &mut Context) {
             for index_edge in 0..n {
                 let e1 = points[index_edge % n];
                 let e2 = points[(index_edge + 1) % n];
-                if e1.x == e2.x
+                if (e1.x == e2.x
                     && e1.x == p1.x
-                    && ((e1.y <= p1.y && p1.y <= e2.y) || e1.y >= p1.y && p1.y >= e2.y)
-                {
-                    on_edge = true;
-                    break;
-                } else if e1.y == e2.y
-                    && e1.y == p1.y
-                    && ((e1.x <= p1.x && p1.x <= e2.x) || (e1.x >= p1.x && p1.x >= e2.x))
+                    && ((e1.y <= p1.y && p1.y <= e2.y) || e1.y >= p1.y && p1.y >= e2.y))
+                    || (e1.y == e2.y
+                        && e1.y == p1.y
+                        && ((e1.x <= p1.x && p1.x <= e2.x) || (e1.x >= p1.x && p1.x >= e2.x)))
                 {
                     on_edge = true;
                     break;
@@ -259,19 +348,71 @@ fn run(context: &mut Context) {
         }
     }
 
-    debug_example!(context, "{outside_points:#?}");
+    trace!(context, "{outside_points:#?}");
 
-    context.result(identify_largest_rect_area(
-        &points,
-        |top_left, bottom_right| {
+    let (area, top_left, bottom_right) =
+        identify_largest_rect(&points, |top_left, bottom_right| {
             !outside_points.iter().any(|p| {
                 top_left.x <= p.x
                     && p.x <= bottom_right.x
                     && top_left.y <= p.y
                     && p.y <= bottom_right.y
             })
-        },
-    ));
+        });
+    context.visualize("part2-rect", || {
+        render_rect(&points, top_left, bottom_right)
+    });
+    context.result(area);
+}
+
+/// Random rectilinear polygons; `size` is the number of red tiles (corners), rounded down to a multiple of 4.
+///
+/// The polygon is built from columns side by side, each spanning from a random bottom to a random top.
+/// All bottoms are below and all tops above the middle, so neighbouring columns always overlap.
+struct Generator;
+
+impl InputGenerator for Generator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let columns = (size / 4).max(1);
+        let half_height = (2 * columns as i64).max(4);
+        let mut xs = vec![rng.range(0..=3)];
+        let (mut tops, mut bottoms) = (Vec::<i64>::new(), Vec::<i64>::new());
+        for _ in 0..columns {
+            xs.push(xs.last().unwrap() + rng.range(1..=5));
+            // Neighbouring columns need different heights, or their corners would not be turns
+            let top = loop {
+                let top = rng.range(half_height + 1..=2 * half_height);
+                if tops.last() != Some(&top) {
+                    break top;
+                }
+            };
+            let bottom = loop {
+                let bottom = rng.range(0..=half_height - 1);
+                if bottoms.last() != Some(&bottom) {
+                    break bottom;
+                }
+            };
+            tops.push(top);
+            bottoms.push(bottom);
+        }
+        // Along the tops from left to right, then back along the bottoms
+        let mut corners = Vec::new();
+        for index in 0..columns {
+            corners.push((xs[index], tops[index]));
+            corners.push((xs[index + 1], tops[index]));
+        }
+        for index in (0..columns).rev() {
+            corners.push((xs[index + 1], bottoms[index]));
+            corners.push((xs[index], bottoms[index]));
+        }
+        let transpose = rng.chance(0.5);
+        corners
+            .into_iter()
+            .map(|(x, y)| if transpose { (y, x) } else { (x, y) })
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -342,4 +483,15 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_check_loop() {
+        assert!(check_loop(INFO.example).is_empty());
+        // Diagonal step from the 2nd to the 3rd corner
+        let lines = check_loop("1,1\n5,1\n6,4\n1,4")
+            .iter()
+            .map(|violation| violation.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3]);
+    }
 }
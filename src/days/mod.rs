@@ -1,21 +1,58 @@
 use crate::DayInfo;
 
+/// Declares the module of each day, compiled only when the day's cargo feature is enabled
 macro_rules! days {
-    ($num:literal) => {
-        use seq_macro::seq;
+    ($($feature:literal => $day:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = $feature)]
+            mod $day;
+        )*
 
-        seq!(N in 1..=$num {
-            pub const DAYS: [DayInfo; $num] = [
-                #(
-                    day_~N::INFO,
-                )*
-            ];
-        });
+        /// Solution of each day, `None` for days whose feature is disabled
+        pub static DAYS: [Option<DayInfo>; [$($feature),*].len()] = [
+            $({
+                #[cfg(feature = $feature)]
+                let day = Some($day::INFO);
+                #[cfg(not(feature = $feature))]
+                let day = None;
+                day
+            }),*
+        ];
 
-        seq!(N in 1..=$num {
-            mod day_~N;
-        });
+        /// Source code of each day, used to detect when a solution changed; empty for disabled days
+        pub const SOURCES: [&str; [$($feature),*].len()] = [
+            $({
+                #[cfg(feature = $feature)]
+                let source = include_str!(concat!(stringify!($day), ".rs"));
+                #[cfg(not(feature = $feature))]
+                let source = "";
+                source
+            }),*
+        ];
     };
 }
 
-days!(12);
+days! {
+    "day-1" => day_1,
+    "day-2" => day_2,
+    "day-3" => day_3,
+    "day-4" => day_4,
+    "day-5" => day_5,
+    "day-6" => day_6,
+    "day-7" => day_7,
+    "day-8" => day_8,
+    "day-9" => day_9,
+    "day-10" => day_10,
+    "day-11" => day_11,
+    "day-12" => day_12,
+}
+
+/// Solution of a day, or `None` if there is no such day or its feature is disabled
+pub fn get(day_num: u8) -> Option<&'static DayInfo> {
+    DAYS.get(usize::from(day_num).checked_sub(1)?)?.as_ref()
+}
+
+/// Number and solution of every enabled day, in order
+pub fn enabled() -> impl Iterator<Item = (u8, &'static DayInfo)> {
+    (1..=DAYS.len() as u8).filter_map(|day_num| Some((day_num, get(day_num)?)))
+}
@@ -0,0 +1,113 @@
+//! Differential tests: the alternative solutions of every day with an input generator are run next to the main
+//! solution on many small generated inputs, and the first input they disagree on is reported, minimized.
+//! A solution panicking on an input the other one solves counts as a disagreement too.
+
+use crate::answer::Answer;
+use crate::days;
+use crate::fuzz::minimize;
+use crate::generate::generate;
+use crate::parallel::catch_panic;
+use crate::solution::Variant;
+use crate::{solve, solve_with};
+use std::mem::discriminant;
+
+/// How the main solution and a variant differ on an input
+#[derive(Debug)]
+enum Mismatch {
+    Answers {
+        main: Vec<Answer>,
+        other: Vec<Answer>,
+    },
+    MainPanicked(String),
+    VariantPanicked(String),
+}
+
+fn mismatch(day_num: u8, input: &str, variant: &dyn Variant) -> Option<Mismatch> {
+    let main = catch_panic(|| solve(day_num, input));
+    let other = catch_panic(|| solve_with(day_num, input, Some(variant)));
+    match (main, other) {
+        (Ok(main), Ok(other)) if main == other => None,
+        (Ok(main), Ok(other)) => Some(Mismatch::Answers { main, other }),
+        (Err(message), _) => Some(Mismatch::MainPanicked(message)),
+        (Ok(_), Err(message)) => Some(Mismatch::VariantPanicked(message)),
+    }
+}
+
+/// Runs a variant next to the main solution on generated inputs, describing the first mismatch, minimized
+fn check_variant(day_num: u8, variant: &dyn Variant) -> Result<(), String> {
+    let day = days::get(day_num).unwrap();
+    for seed in 0..60 {
+        let input = generate(day_num, 1 + seed as usize % 6, seed).unwrap();
+        let Some(found) = mismatch(day_num, &input, variant) else {
+            continue;
+        };
+        // Keep the same kind of mismatch, on inputs the parser still accepts
+        let input = minimize(&input, |input| {
+            (day.parse)(input).is_ok()
+                && mismatch(day_num, input, variant)
+                    .is_some_and(|other| discriminant(&other) == discriminant(&found))
+        });
+        let found = mismatch(day_num, &input, variant).unwrap_or(found);
+        let name = variant.name();
+        let details = match found {
+            Mismatch::Answers { main, other } => format!("main: {main:?}\n{name}: {other:?}"),
+            Mismatch::MainPanicked(message) => format!("main solution panicked: {message}"),
+            Mismatch::VariantPanicked(message) => format!("{name} panicked: {message}"),
+        };
+        return Err(format!(
+            "day {day_num}: {name} disagrees with the main solution (seed {seed}) on\n{input}\n{details}"
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn variants_agree_on_generated_inputs() {
+    for (day_num, day) in days::enabled() {
+        if day.generator.is_none() {
+            continue;
+        }
+        for variant in day.variants {
+            if let Err(message) = check_variant(day_num, *variant) {
+                panic!("{message}");
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "day-1")]
+fn panics_count_as_disagreement() {
+    use crate::Context;
+    use crate::parse::ParseError;
+    use crate::solution::Solution;
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        type Input = ();
+
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &(), _context: &Context) -> Answer {
+            panic!("not implemented")
+        }
+
+        fn part2(&self, _input: &(), _context: &Context) -> Answer {
+            Answer::NotApplicable
+        }
+    }
+
+    let message = check_variant(1, &Panicking).unwrap_err();
+    assert!(message.contains("(seed 0)"), "{message}");
+    assert!(
+        message.contains("panicking panicked: not implemented"),
+        "{message}"
+    );
+}
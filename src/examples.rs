@@ -0,0 +1,98 @@
+//! Extra example inputs, stored as `data/<day>/examples/<name>.txt` and run in examples mode alongside the
+//! built-in example.
+//!
+//! An example can have its expected answers in `<name>.expected`: one line per part, part 1 first.
+//! A `?`, empty or missing line means the answer is unknown, only `n/a` that the part has no answer,
+//! and `\n` can be used for multi-line answers.
+
+use crate::answer::Answer;
+use crate::history::unescape;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleFile {
+    /// File name without the directory, e.g. `large.txt`
+    pub name: String,
+    pub input: String,
+    /// Expected answer of each part, where known
+    pub expected: Vec<Option<Answer>>,
+}
+
+/// Loads the extra examples of a day, sorted by name; returns an empty list if there are none
+pub fn load(day_dir: &Path) -> Result<Vec<ExampleFile>, std::io::Error> {
+    let entries = match fs::read_dir(day_dir.join("examples")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let expected = match fs::read_to_string(path.with_extension("expected")) {
+            Ok(text) => parse_expected(&text),
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        examples.push(ExampleFile {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            input: fs::read_to_string(&path)?,
+            expected,
+        });
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+fn parse_expected(text: &str) -> Vec<Option<Answer>> {
+    text.lines()
+        .map(|line| match line.trim_end() {
+            "?" | "" => None,
+            line => Some(unescape(line).parse().unwrap()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_expected_answers() {
+        assert_eq!(parse_expected("42\n?\n"), [Some(Answer::Integer(42)), None]);
+        assert_eq!(parse_expected("\n7"), [None, Some(Answer::Integer(7))]);
+        assert_eq!(
+            parse_expected("#.\\n.#\nn/a"),
+            [
+                Some(Answer::Art("#.\n.#".to_string())),
+                Some(Answer::NotApplicable)
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_examples() {
+        let day_dir =
+            std::env::temp_dir().join(format!("aoc-examples-test-{}", std::process::id()));
+        let dir = day_dir.join("examples");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.txt"), "2").unwrap();
+        fs::write(dir.join("a.txt"), "1").unwrap();
+        fs::write(dir.join("a.expected"), "10\n20").unwrap();
+        fs::write(dir.join("notes.md"), "ignored").unwrap();
+        let examples = load(&day_dir).unwrap();
+        fs::remove_dir_all(&day_dir).unwrap();
+
+        let names = examples.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a.txt", "b.txt"]);
+        assert_eq!(
+            examples[0].expected,
+            [Some(Answer::Integer(10)), Some(Answer::Integer(20))]
+        );
+        assert!(examples[1].expected.is_empty());
+    }
+}
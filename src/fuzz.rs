@@ -0,0 +1,205 @@
+//! Built-in fuzzer for the parsing stage of each day ([`DayInfo::parse`](crate::DayInfo::parse)).
+//!
+//! Starting from a corpus seeded with the day's example (and a small generated input, for days with a generator),
+//! the parser is fed randomly mutated inputs, which must be either accepted or rejected with a
+//! [`ParseError`](crate::parse::ParseError). Accepted inputs join the corpus, so later mutations build on inputs
+//! that get past the first checks.
+
+use crate::day_info;
+use crate::generate::generate;
+use crate::parallel::catch_panic;
+use crate::utils::random::Rng;
+
+/// Fragments inserted by the mutator: separators and characters used by the puzzle inputs, and troublesome numbers
+const TOKENS: &[&str] = &[
+    "0",
+    "1",
+    "9",
+    "-1",
+    "00",
+    "4294967296",
+    "99999999999999999999999",
+    ",",
+    "-",
+    ":",
+    ": ",
+    " ",
+    "\n",
+    "\n\n",
+    "x",
+    "#",
+    ".",
+    "@",
+    "^",
+    "S",
+    "L",
+    "R",
+    "+",
+    "*",
+    "(",
+    ")",
+    "[",
+    "]",
+    "{",
+    "}",
+    "é",
+];
+
+/// Most corpus entries kept
+const MAX_CORPUS: usize = 1000;
+
+/// Input that made a parser panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    /// Smallest input found that still panics
+    pub input: String,
+    pub message: String,
+}
+
+/// What a fuzzing session went through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FuzzStats {
+    pub accepted: usize,
+    pub rejected: usize,
+    /// Size of the corpus at the end
+    pub corpus: usize,
+}
+
+/// Runs the parser of a day on `iterations` mutated inputs, stopping at the first one that makes it panic
+pub fn fuzz(day_num: u8, iterations: usize, seed: u64) -> Result<FuzzStats, Crash> {
+    let day = day_info(day_num);
+    let panics = |input: &str| catch_panic(|| (day.parse)(input)).err();
+
+    let mut rng = Rng::new(seed);
+    let mut corpus = vec![day.example.to_string()];
+    corpus.extend(generate(day_num, 5, seed));
+    let mut stats = FuzzStats::default();
+    for _ in 0..iterations {
+        let mut input = corpus[rng.below(corpus.len() as u64) as usize].clone();
+        for _ in 0..rng.range(1..=4) {
+            input = mutate(&input, &mut rng);
+        }
+        match catch_panic(|| (day.parse)(&input)) {
+            Ok(Ok(())) => {
+                stats.accepted += 1;
+                if corpus.len() < MAX_CORPUS && !corpus.contains(&input) {
+                    corpus.push(input);
+                }
+            }
+            Ok(Err(_)) => stats.rejected += 1,
+            Err(_) => {
+                let input = minimize(&input, |input| panics(input).is_some());
+                let message = panics(&input).unwrap();
+                return Err(Crash { input, message });
+            }
+        }
+    }
+    stats.corpus = corpus.len();
+    Ok(stats)
+}
+
+/// Applies a random change to the input
+fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let position = |rng: &mut Rng, chars: &Vec<char>| rng.below(chars.len() as u64 + 1) as usize;
+    match rng.below(6) {
+        // Delete a span
+        0 => {
+            let start = position(rng, &chars);
+            let end = (start + rng.range(1..=8) as usize).min(chars.len());
+            chars.drain(start..end);
+        }
+        // Duplicate a span
+        1 => {
+            let start = position(rng, &chars);
+            let end = (start + rng.range(1..=16) as usize).min(chars.len());
+            let span = chars[start..end].to_vec();
+            chars.splice(start..start, span);
+        }
+        // Replace a character
+        2 if !chars.is_empty() => {
+            let index = rng.below(chars.len() as u64) as usize;
+            let token = TOKENS[rng.below(TOKENS.len() as u64) as usize];
+            chars[index] = token.chars().next().unwrap();
+        }
+        // Cut the input short
+        3 => chars.truncate(position(rng, &chars)),
+        // Delete or duplicate a whole line
+        4 => {
+            let mut lines = input.lines().collect::<Vec<_>>();
+            if !lines.is_empty() {
+                let index = rng.below(lines.len() as u64) as usize;
+                if rng.chance(0.5) {
+                    lines.remove(index);
+                } else {
+                    lines.insert(index, lines[index]);
+                }
+            }
+            return lines.join("\n");
+        }
+        // Insert a token
+        _ => {
+            let token = TOKENS[rng.below(TOKENS.len() as u64) as usize];
+            let start = position(rng, &chars);
+            chars.splice(start..start, token.chars());
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Shrinks the input while it stays interesting: removes as many lines as possible, large chunks first,
+/// then as many characters as possible
+pub(crate) fn minimize(input: &str, interesting: impl Fn(&str) -> bool) -> String {
+    let lines = shrink(input.lines().collect(), |lines| {
+        interesting(&lines.join("\n"))
+    });
+    let chars = shrink(lines.join("\n").chars().collect(), |chars| {
+        interesting(&chars.iter().collect::<String>())
+    });
+    chars.into_iter().collect()
+}
+
+/// Removes chunks of items, halving the chunk size whenever no chunk of the current size can be removed
+fn shrink<T: Clone>(mut items: Vec<T>, interesting: impl Fn(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = items.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+            if interesting(&candidate) {
+                items = candidate;
+            } else {
+                start = end;
+            }
+        }
+        chunk /= 2;
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn parsers_reject_malformed_inputs() {
+        for (day, info) in days::enabled() {
+            assert_eq!((info.parse)(info.example), Ok(()), "example of day {day}");
+            if let Err(crash) = fuzz(day, 2000, u64::from(day)) {
+                panic!(
+                    "parser of day {day} panicked: {}\non input:\n{}",
+                    crash.message, crash.input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn minimizes_input() {
+        let input = "1\n2\n3\n4\n5\n6\n7";
+        let interesting = |input: &str| input.contains('3') && input.contains('6');
+        assert_eq!(minimize(input, interesting), "36");
+    }
+}
@@ -0,0 +1,36 @@
+//! Random puzzle inputs for scaling and stress tests, produced by the [`InputGenerator`] of a day.
+
+use crate::days;
+use crate::utils::random::Rng;
+
+/// Produces random, valid inputs for a day
+pub trait InputGenerator: Sync {
+    /// Generates an input; what `size` counts depends on the day, e.g. lines, ranges or polygon corners
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
+
+/// Generates an input for a day, or returns `None` if the day has no generator
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let generator = days::get(day)?.generator?;
+    Some(generator.generate(size, &mut Rng::new(seed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for (day, info) in days::enabled() {
+            if info.generator.is_none() {
+                continue;
+            }
+            assert_eq!(generate(day, 40, 3), generate(day, 40, 3));
+            for seed in 0..5 {
+                let input = generate(day, 40, seed).unwrap();
+                assert_eq!(solve(day, &input).len(), 2, "day {day}, seed {seed}");
+            }
+        }
+    }
+}
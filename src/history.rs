@@ -0,0 +1,275 @@
+//! Per-day history of full runs, stored in `data/<day>/history.txt`.
+//!
+//! Each line holds one run, as tab-separated fields: timestamp (seconds since the Unix epoch), part (1 or 2),
+//! source hash, elapsed nanoseconds, then one field per answer. Tabs, newlines and backslashes in answers are escaped.
+
+use crate::Part;
+use crate::answer::Answer;
+use crate::days::SOURCES;
+use std::fmt::{Display, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub part: Part,
+    /// Hash of the day's source file, see [`source_hash`]
+    pub source_hash: u64,
+    pub elapsed: Duration,
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("could not access history file")]
+    Io(#[from] std::io::Error),
+    #[error("invalid history entry on line {0}")]
+    InvalidEntry(usize),
+}
+
+impl HistoryEntry {
+    /// Creates an entry for a run that just finished
+    pub(crate) fn now(day: u8, part: Part, elapsed: Duration, answers: Vec<Answer>) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            part,
+            source_hash: source_hash(day),
+            elapsed,
+            answers,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let mut fields = vec![
+            self.timestamp.to_string(),
+            (self.part as u8).to_string(),
+            format!("{:016x}", self.source_hash),
+            self.elapsed.as_nanos().to_string(),
+        ];
+        fields.extend(
+            self.answers
+                .iter()
+                .map(|answer| escape(&answer.to_string())),
+        );
+        fields.join("\t")
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let part = match fields.next()? {
+            "1" => Part::One,
+            "2" => Part::Both,
+            _ => return None,
+        };
+        let source_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+        let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+        let answers = fields
+            .map(|field| unescape(field).parse().unwrap())
+            .collect();
+        Some(Self {
+            timestamp,
+            part,
+            source_hash,
+            elapsed,
+            answers,
+        })
+    }
+}
+
+/// Stable (FNV-1a) hash of a day's source file, embedded at compile time.
+/// Unlike a commit hash, this only changes when the day's own code changes.
+pub fn source_hash(day: u8) -> u64 {
+    SOURCES[day as usize - 1]
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+pub(crate) fn append(day_dir: &Path, entry: &HistoryEntry) -> Result<(), HistoryError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(day_dir.join("history.txt"))?;
+    writeln!(file, "{}", entry.to_line())?;
+    Ok(())
+}
+
+/// Loads all entries of a day, oldest first; returns an empty list if there is no history yet
+pub fn load(day_dir: &Path) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let file = match File::open(day_dir.join("history.txt")) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.is_empty()))
+        .map(|(index, line)| {
+            HistoryEntry::from_line(&line?).ok_or(HistoryError::InvalidEntry(index + 1))
+        })
+        .collect()
+}
+
+/// Runtime changes smaller than this fraction are considered noise
+const RUNTIME_CHANGE_THRESHOLD: f64 = 0.25;
+
+/// What changed between a run and the previous run of the same part
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Changes {
+    pub first_run: bool,
+    pub answers: bool,
+    pub source: bool,
+    pub runtime: bool,
+}
+
+impl Changes {
+    pub fn any(&self) -> bool {
+        self.first_run || self.answers || self.source || self.runtime
+    }
+}
+
+/// Pairs each entry with what changed since the previous entry for the same part
+pub fn changes(entries: &[HistoryEntry]) -> Vec<(&HistoryEntry, Changes)> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let previous = entries[..index].iter().rev().find(|e| e.part == entry.part);
+            let changes = match previous {
+                None => Changes {
+                    first_run: true,
+                    ..Default::default()
+                },
+                Some(previous) => {
+                    let (old, new) = (previous.elapsed.as_secs_f64(), entry.elapsed.as_secs_f64());
+                    Changes {
+                        first_run: false,
+                        answers: previous.answers != entry.answers,
+                        source: previous.source_hash != entry.source_hash,
+                        runtime: (new - old).abs() > old * RUNTIME_CHANGE_THRESHOLD,
+                    }
+                }
+            };
+            (entry, changes)
+        })
+        .collect()
+}
+
+/// Formats a Unix timestamp as a UTC date & time, e.g. `2025-12-01 05:00:00`
+pub struct Timestamp(pub u64);
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let days = (self.0 / 86400) as i64;
+        let seconds = self.0 % 86400;
+        // Civil-from-days algorithm, see https://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+pub(crate) fn unescape(field: &str) -> String {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            answer.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => answer.push('\t'),
+            Some('n') => answer.push('\n'),
+            Some(other) => answer.push(other),
+            None => answer.push('\\'),
+        }
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, millis: u64, answers: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            part: Part::Both,
+            source_hash: 0xabc,
+            elapsed: Duration::from_millis(millis),
+            answers: answers.iter().map(|s| s.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let entry = entry(1764565200, 12, &["42", "a\tb\nc\\d"]);
+        assert_eq!(HistoryEntry::from_line(&entry.to_line()), Some(entry));
+    }
+
+    #[test]
+    fn detects_changes() {
+        let entries = [
+            entry(1, 100, &["1", "2"]),
+            entry(2, 110, &["1", "2"]),
+            entry(3, 300, &["1", "2"]),
+            entry(4, 300, &["1", "3"]),
+        ];
+        let changes = changes(&entries)
+            .into_iter()
+            .map(|(_, changes)| (changes.first_run, changes.answers, changes.runtime))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (true, false, false),
+                (false, false, false),
+                (false, false, true),
+                (false, true, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_timestamp() {
+        assert_eq!(Timestamp(0).to_string(), "1970-01-01 00:00:00");
+        assert_eq!(Timestamp(1764565200).to_string(), "2025-12-01 05:00:00");
+        assert_eq!(Timestamp(951782400).to_string(), "2000-02-29 00:00:00");
+    }
+}
@@ -0,0 +1,126 @@
+//! Access to the puzzle server. Requests go through an [`HttpBackend`], so they can be served by
+//! something other than the real website, e.g. a local stand-in server in tests.
+
+use std::env;
+use thiserror::Error;
+
+/// Performs the actual HTTP requests; [`UreqBackend`] is used unless another backend is provided
+pub trait HttpBackend: Send + Sync {
+    /// Gets a page with the given `Cookie` header, returning the response body
+    fn get(&self, url: &str, cookie: &str) -> Result<String, HttpError>;
+
+    /// Posts a URL-encoded form with the given `Cookie` header, returning the response body
+    fn post_form(
+        &self,
+        url: &str,
+        cookie: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, HttpError>;
+}
+
+#[derive(Debug, Error)]
+pub enum HttpError {
+    #[error("no session token, set the AOC_SESSION environment variable or `session` in aoc.toml")]
+    MissingSession,
+    #[error("server responded with status {0}")]
+    Status(u16),
+    #[error("request failed: {0}")]
+    Request(String),
+}
+
+/// Blocking backend built on `ureq`, supports both `http` and `https` URLs
+pub struct UreqBackend {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqBackend {
+    fn default() -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+        }
+    }
+}
+
+impl From<ureq::Error> for HttpError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::StatusCode(status) => HttpError::Status(status),
+            err => HttpError::Request(err.to_string()),
+        }
+    }
+}
+
+impl HttpBackend for UreqBackend {
+    fn get(&self, url: &str, cookie: &str) -> Result<String, HttpError> {
+        Ok(self
+            .agent
+            .get(url)
+            .header("Cookie", cookie)
+            .call()?
+            .body_mut()
+            .read_to_string()?)
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        cookie: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, HttpError> {
+        Ok(self
+            .agent
+            .post(url)
+            .header("Cookie", cookie)
+            .send_form(form.iter().copied())?
+            .body_mut()
+            .read_to_string()?)
+    }
+}
+
+/// Where the puzzles live and who we are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Server {
+    /// Scheme and host without a trailing slash, e.g. `https://adventofcode.com`
+    pub base_url: String,
+    pub year: u16,
+    /// Value of the `session` cookie of a logged-in browser
+    pub session: Option<String>,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self {
+            base_url: Self::DEFAULT_BASE_URL.to_string(),
+            year: Self::DEFAULT_YEAR,
+            session: None,
+        }
+    }
+}
+
+impl Server {
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+    pub const DEFAULT_YEAR: u16 = 2025;
+
+    /// Takes the session token from `AOC_SESSION` and the base URL from `AOC_BASE_URL`, if they are set
+    pub fn apply_env(&mut self) {
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            self.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if let Some(session) = env::var("AOC_SESSION")
+            .ok()
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty())
+        {
+            self.session = Some(session);
+        }
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day)
+    }
+
+    pub(crate) fn cookie(&self) -> Result<String, HttpError> {
+        let session = self.session.as_ref().ok_or(HttpError::MissingSession)?;
+        Ok(format!("session={session}"))
+    }
+}
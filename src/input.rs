@@ -0,0 +1,132 @@
+//! Puzzle inputs, cached in `data/<day>/input.txt`. A missing input is downloaded from the puzzle server once;
+//! after that the cached file is always used, so the server is never asked twice for the same input.
+
+use crate::http::{HttpBackend, HttpError, Server, UreqBackend};
+use std::fmt::{Debug, Formatter};
+use std::fs::{self, File};
+use std::io::{BufReader, ErrorKind};
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("could not access input file")]
+    Io(#[from] std::io::Error),
+    #[error("could not download input: {0}")]
+    Http(#[from] HttpError),
+}
+
+/// Loads inputs from the cache, downloading them when needed
+#[derive(Clone)]
+pub struct InputProvider {
+    pub server: Server,
+    pub backend: Arc<dyn HttpBackend>,
+}
+
+impl Default for InputProvider {
+    fn default() -> Self {
+        Self {
+            server: Server::default(),
+            backend: Arc::new(UreqBackend::default()),
+        }
+    }
+}
+
+impl Debug for InputProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputProvider")
+            .field("server", &self.server)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InputProvider {
+    pub fn load(&self, day_dir: &Path, day: u8) -> Result<String, InputError> {
+        let path = day_dir.join("input.txt");
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        let url = format!("{}/input", self.server.day_url(day));
+        let input = self.backend.get(&url, &self.server.cookie()?)?;
+        fs::create_dir_all(day_dir)?;
+        // Write to a temporary file first, so an interrupted write doesn't leave a truncated input behind
+        let partial = day_dir.join("input.txt.partial");
+        fs::write(&partial, &input)?;
+        fs::rename(partial, path)?;
+        Ok(input)
+    }
+
+    /// Like [`InputProvider::load`], but opens the input file for reading instead of loading it in memory
+    pub fn open(&self, day_dir: &Path, day: u8) -> Result<BufReader<File>, InputError> {
+        let path = day_dir.join("input.txt");
+        match File::open(&path) {
+            Ok(file) => return Ok(BufReader::new(file)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        self.load(day_dir, day)?;
+        Ok(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Serves the same page for every request, recording the requested URLs
+    #[derive(Default)]
+    struct MockBackend {
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl HttpBackend for MockBackend {
+        fn get(&self, url: &str, cookie: &str) -> Result<String, HttpError> {
+            assert_eq!(cookie, "session=abc");
+            self.requests.lock().unwrap().push(url.to_string());
+            Ok("1 2 3\n".to_string())
+        }
+
+        fn post_form(&self, _: &str, _: &str, _: &[(&str, &str)]) -> Result<String, HttpError> {
+            // Inputs are only ever downloaded
+            Err(HttpError::Request("unexpected form post".to_string()))
+        }
+    }
+
+    #[test]
+    fn downloads_once() {
+        let day_dir = std::env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+        let backend = Arc::new(MockBackend::default());
+        let provider = InputProvider {
+            server: Server {
+                base_url: "http://localhost:1234".to_string(),
+                year: 2025,
+                session: Some("abc".to_string()),
+            },
+            backend: backend.clone(),
+        };
+        let first = provider.load(&day_dir, 5).unwrap();
+        let second = provider.load(&day_dir, 5).unwrap();
+        let cached = fs::read_to_string(day_dir.join("input.txt")).unwrap();
+        fs::remove_dir_all(&day_dir).unwrap();
+
+        assert_eq!((first.as_str(), second.as_str()), ("1 2 3\n", "1 2 3\n"));
+        assert_eq!(cached, "1 2 3\n");
+        assert_eq!(
+            *backend.requests.lock().unwrap(),
+            ["http://localhost:1234/2025/day/5/input"]
+        );
+    }
+
+    #[test]
+    fn requires_session() {
+        let day_dir =
+            std::env::temp_dir().join(format!("aoc-input-test-none-{}", std::process::id()));
+        let error = InputProvider::default().load(&day_dir, 1).unwrap_err();
+        assert!(matches!(error, InputError::Http(HttpError::MissingSession)));
+        assert!(!day_dir.exists());
+    }
+}
@@ -0,0 +1,230 @@
+//! Summary of the shape of an input, shown by the `inspect` command: lines and their lengths, characters,
+//! blank-line separated sections, grids and numbers.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Statistics about an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStats {
+    pub lines: usize,
+    /// Number of lines of each length
+    pub lengths: BTreeMap<usize, usize>,
+    /// Number of occurrences of each character, excluding line breaks
+    pub chars: BTreeMap<char, usize>,
+    pub sections: Vec<Section>,
+    pub numbers: Option<NumberStats>,
+}
+
+/// Lines between blank lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// 1-based line number of the first line
+    pub first_line: usize,
+    pub lines: usize,
+    /// Width and height, if all lines of the section have the same length and the section looks like a grid
+    pub grid: Option<(usize, usize)>,
+}
+
+/// Integers found in the input: runs of digits, with a `-` sign if it doesn't follow a letter or digit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberStats {
+    pub count: usize,
+    pub min: i128,
+    pub max: i128,
+    /// Most digits of any number, which may not fit in `min` and `max`
+    pub max_digits: usize,
+}
+
+impl InputStats {
+    pub fn of(input: &str) -> Self {
+        let mut lengths = BTreeMap::new();
+        let mut chars = BTreeMap::new();
+        let mut sections = Vec::new();
+        let mut section_lengths = Vec::new();
+        let lines = input.lines().collect::<Vec<_>>();
+        for (index, line) in lines.iter().enumerate() {
+            *lengths.entry(line.chars().count()).or_default() += 1;
+            for char in line.chars() {
+                *chars.entry(char).or_default() += 1;
+            }
+            if !line.is_empty() {
+                section_lengths.push(line.chars().count());
+            }
+            let last = index + 1 == lines.len();
+            if (line.is_empty() || last) && !section_lengths.is_empty() {
+                let end = if line.is_empty() { index } else { index + 1 };
+                sections.push(Section::new(
+                    end - section_lengths.len() + 1,
+                    &section_lengths,
+                ));
+                section_lengths.clear();
+            }
+        }
+        Self {
+            lines: lines.len(),
+            lengths,
+            chars,
+            sections,
+            numbers: NumberStats::of(input),
+        }
+    }
+}
+
+impl Section {
+    fn new(first_line: usize, lengths: &[usize]) -> Self {
+        // A single line, or lines of a single character, are more likely a list than a grid
+        let width = lengths[0];
+        let grid =
+            (lengths.len() > 1 && width > 1 && lengths.iter().all(|&length| length == width))
+                .then_some((width, lengths.len()));
+        Self {
+            first_line,
+            lines: lengths.len(),
+            grid,
+        }
+    }
+}
+
+impl NumberStats {
+    fn of(input: &str) -> Option<Self> {
+        let mut stats: Option<Self> = None;
+        let mut previous = None;
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, char)) = chars.next() {
+            let negative = char == '-'
+                && previous.is_none_or(|previous: char| !previous.is_alphanumeric())
+                && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit());
+            previous = Some(char);
+            if !char.is_ascii_digit() && !negative {
+                continue;
+            }
+            let mut end = start + char.len_utf8();
+            while let Some(&(index, digit)) = chars.peek()
+                && digit.is_ascii_digit()
+            {
+                end = index + 1;
+                previous = Some(digit);
+                chars.next();
+            }
+            let text = &input[start..end];
+            let digits = text.trim_start_matches('-').len();
+            // Numbers too large for an i128 still count towards the digits
+            let value = text.parse::<i128>().ok();
+            let stats = stats.get_or_insert(Self {
+                count: 0,
+                min: i128::MAX,
+                max: i128::MIN,
+                max_digits: 0,
+            });
+            stats.count += 1;
+            stats.max_digits = stats.max_digits.max(digits);
+            if let Some(value) = value {
+                stats.min = stats.min.min(value);
+                stats.max = stats.max.max(value);
+            }
+        }
+        stats
+    }
+}
+
+impl Display for InputStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "lines: {}", self.lines)?;
+        if let (Some((min, _)), Some((max, _))) = (
+            self.lengths.first_key_value(),
+            self.lengths.last_key_value(),
+        ) {
+            let mut common = self.lengths.iter().collect::<Vec<_>>();
+            common.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            let common = common
+                .iter()
+                .take(5)
+                .map(|(length, count)| format!("{length} ({count})"))
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "line lengths: {min} to {max}, most common: {}",
+                common.join(", ")
+            )?;
+        }
+        let mut chars = self.chars.iter().collect::<Vec<_>>();
+        chars.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        writeln!(f, "characters: {} distinct", chars.len())?;
+        for (char, count) in chars.iter().take(20) {
+            writeln!(f, "  {:<8} {count}", char.escape_debug().to_string())?;
+        }
+        if chars.len() > 20 {
+            writeln!(f, "  ... {} more", chars.len() - 20)?;
+        }
+        writeln!(f, "sections: {}", self.sections.len())?;
+        for section in &self.sections {
+            let last_line = section.first_line + section.lines - 1;
+            write!(
+                f,
+                "  lines {}-{last_line} ({} lines)",
+                section.first_line, section.lines
+            )?;
+            match section.grid {
+                Some((width, height)) => writeln!(f, ", grid {width}x{height}")?,
+                None => writeln!(f)?,
+            }
+        }
+        match &self.numbers {
+            Some(numbers) if numbers.min <= numbers.max => writeln!(
+                f,
+                "numbers: {} from {} to {}, up to {} digits",
+                numbers.count, numbers.min, numbers.max, numbers.max_digits
+            ),
+            Some(numbers) => writeln!(
+                f,
+                "numbers: {}, up to {} digits",
+                numbers.count, numbers.max_digits
+            ),
+            None => writeln!(f, "numbers: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_input() {
+        let stats = InputStats::of("3-5\n10-14\n\n..#\n#..\n\nR-7");
+        assert_eq!(stats.lines, 7);
+        assert_eq!(stats.lengths[&3], 4);
+        assert_eq!(stats.chars[&'-'], 3);
+        assert_eq!(
+            stats.sections,
+            [
+                Section {
+                    first_line: 1,
+                    lines: 2,
+                    grid: None
+                },
+                Section {
+                    first_line: 4,
+                    lines: 2,
+                    grid: Some((3, 2))
+                },
+                Section {
+                    first_line: 7,
+                    lines: 1,
+                    grid: None
+                },
+            ]
+        );
+        assert_eq!(
+            stats.numbers,
+            Some(NumberStats {
+                count: 5,
+                min: 3,
+                max: 14,
+                max_digits: 2
+            })
+        );
+        assert_eq!(InputStats::of("x -12 9").numbers.unwrap().min, -12);
+    }
+}
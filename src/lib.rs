@@ -1,16 +1,67 @@
+pub mod answer;
+pub mod config;
+pub mod counters;
 mod days;
+#[cfg(test)]
+mod differential;
+pub mod examples;
+pub mod fuzz;
+pub mod generate;
+pub mod history;
+pub mod http;
+pub mod input;
+pub mod inspect;
+pub mod logging;
+pub mod memory;
+mod parallel;
+pub mod parse;
+mod progress;
+mod report;
+pub mod serve;
+pub mod solution;
+pub mod stream;
+pub mod submit;
+pub mod utils;
+pub mod validate;
+pub mod viz;
 
 use crate::Part::{Both, One};
 use crate::RunType::{Examples, Full};
+use answer::Answer;
+use counters::Counts;
 use days::DAYS;
-use io_tee::TeeWriter;
-use std::fmt::Display;
+use examples::ExampleFile;
+use generate::InputGenerator;
+use history::{HistoryEntry, Timestamp};
+use input::InputProvider;
+use inspect::InputStats;
+use logging::{Level, LogOptions, LogSink, LogTarget, Logger};
+use memory::{MemoryStats, MemoryTracker};
+use parse::ParseError;
+use progress::Progress;
+use report::{HumanDuration, Report};
+use serde::Deserialize;
+use solution::Variant;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Arguments, Display};
 use std::fs::File;
-use std::io::{Read, Write, stdout};
-use std::path::Path;
-use std::time::Instant;
+use std::io::{BufRead, IsTerminal, Write, sink, stderr, stdout};
+use std::iter::once;
+use std::mem::take;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{RecvTimeoutError, channel};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use stream::InputLines;
+use validate::Rule;
+use viz::{Frame, Visualizer};
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RunType {
     Examples = 1,
     Full = 2,
@@ -27,12 +78,24 @@ pub struct DayInfo {
     pub name: &'static str,
     /// Day solution, use `result()` to output the final result
     pub run: fn(input: &mut Context) -> (),
+    /// Parsing stage of `run`, checked by the `fuzz` command to reject malformed input without panicking
+    pub parse: fn(input: &str) -> Result<(), ParseError>,
     /// Example input
     pub example: &'static str,
+    /// Generates random inputs for stress tests, see the `gen` command
+    pub generator: Option<&'static dyn InputGenerator>,
+    /// Structural preconditions of the input, checked before `run`
+    pub validator: Option<&'static [Rule]>,
+    /// Whether `run` reads the input only through [`Context::lines`], so that the full input file is never loaded
+    /// in memory; [`Context::input`] is then empty, and the validator is skipped since it needs the whole input.
+    /// Examples and alternative solutions still get the input in memory, and are validated
+    pub streaming: bool,
+    /// Alternative solutions, run next to `run` by the `compare` command
+    pub variants: &'static [&'static dyn Variant],
 }
 
 pub struct Context<'a> {
-    /// Problem input
+    /// Problem input, empty for streaming days (see [`DayInfo::streaming`])
     pub input: &'a str,
     /// Whether running just part 1 or both parts
     pub part: Part,
@@ -40,136 +103,816 @@ pub struct Context<'a> {
     /// Should generally not be used, unless the problem statement clearly differs between the two.
     pub run_type: RunType,
     day: &'a DayInfo,
-    result_count: u8,
+    results: Vec<Answer>,
     output: &'a mut dyn Write,
+    logger: Logger,
+    visualizer: Visualizer,
+    cancelled: Arc<AtomicBool>,
+    memory_tracker: MemoryTracker,
+    memory: Vec<MemoryStats>,
+    part_start: Instant,
+    part_times: Vec<Duration>,
+    /// Counters of the current part, see [`Context::count`]
+    counts: RefCell<Counts>,
+    part_counts: Vec<Counts>,
+    params: Option<&'a HashMap<String, String>>,
+    /// Taken by the first call to [`Context::lines`]
+    lines: Option<InputLines<'a>>,
+    progress: Progress,
+}
+
+/// How the runner reports results on stdout
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable output, including everything the solutions print
+    #[default]
+    Text,
+    /// One JSON object per day, nothing else
+    Json,
+}
+
+/// Settings for a single day that replace the ones for all days
+#[derive(Debug, Clone, Default)]
+pub struct DayOverrides {
+    pub timeout: Option<Duration>,
+    /// Values for [`Context::param`], as text
+    pub params: HashMap<String, String>,
+}
+
+/// Options shared by all days of a run
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub log: LogOptions,
+    /// Save visualization frames under `data/<day>/viz/`
+    pub visualize: bool,
+    /// Number of days to run concurrently in `run_all`; 1 runs them sequentially
+    pub jobs: usize,
+    /// Time budget for each day; a day running longer is reported as timed out and asked to cancel
+    pub timeout: Option<Duration>,
+    /// Where full runs get their input from when `data/<day>/input.txt` doesn't exist yet
+    pub inputs: InputProvider,
+    /// Directory holding a subdirectory with the files of each day
+    pub data_dir: PathBuf,
+    pub format: OutputFormat,
+    /// Whether to use colors; `None` to use them only when stdout is a terminal
+    pub color: Option<bool>,
+    /// Per-day settings, keyed by day number
+    pub days: HashMap<u8, DayOverrides>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            log: LogOptions::default(),
+            visualize: false,
+            jobs: 1,
+            timeout: None,
+            inputs: InputProvider::default(),
+            data_dir: PathBuf::from("data"),
+            format: OutputFormat::Text,
+            color: None,
+            days: HashMap::new(),
+        }
+    }
+}
+
+impl RunOptions {
+    /// Directory containing the files of a day: `<data dir>/<day>`
+    pub fn day_dir(&self, day_num: u8) -> PathBuf {
+        self.data_dir.join(day_num.to_string())
+    }
+
+    /// Time budget of a day, taking its overrides into account
+    pub fn timeout_for(&self, day_num: u8) -> Option<Duration> {
+        self.days
+            .get(&day_num)
+            .and_then(|day| day.timeout)
+            .or(self.timeout)
+    }
+
+    pub fn color_enabled(&self) -> bool {
+        self.color.unwrap_or_else(|| stdout().is_terminal())
+    }
+
+    /// Whether to draw the live progress line: only in a terminal, and when logs don't go to the same place
+    pub fn progress_enabled(&self) -> bool {
+        let logging_to_stderr = self.log.level.is_some() && self.log.target == LogTarget::Stderr;
+        stderr().is_terminal() && !logging_to_stderr
+    }
 }
 
 impl<'a> Context<'a> {
-    fn result<T: Display>(&mut self, result: T) {
-        if self.part == One && self.result_count > 0 {
+    /// Context running a day on an in-memory input, without logs, visualizations, parameters or progress line
+    fn new(
+        input: &'a str,
+        day_num: u8,
+        day: &'a DayInfo,
+        run_type: RunType,
+        part: Part,
+        output: &'a mut dyn Write,
+    ) -> Self {
+        Context {
+            input,
+            part,
+            run_type,
+            day,
+            results: Vec::new(),
+            output,
+            logger: Logger::new(day_num, &LogOptions::default(), None),
+            visualizer: Visualizer::new(None),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            memory_tracker: MemoryTracker::start(),
+            memory: Vec::new(),
+            part_start: Instant::now(),
+            part_times: Vec::new(),
+            counts: RefCell::default(),
+            part_counts: Vec::new(),
+            params: None,
+            lines: Some(InputLines::new(input)),
+            progress: Progress::new(day_num, false),
+        }
+    }
+
+    fn result(&mut self, result: impl Into<Answer>) {
+        if self.part == One && !self.results.is_empty() {
             // do nothing if trying to provide part 2 answer when running only part 1
             return;
-        } else if self.result_count > 1 {
+        } else if self.results.len() > 1 {
             panic!("Cannot call `context.result()` more than twice")
         }
-        self.result_count += 1;
-        writeln!(self.output, "Part {} result: {}", self.result_count, result).unwrap();
+        // Everything since the previous result belongs to this part
+        if let Some(stats) = self.memory_tracker.finish() {
+            self.memory.push(stats);
+            self.memory_tracker = MemoryTracker::start();
+        }
+        self.part_times.push(self.part_start.elapsed());
+        self.part_counts.push(take(self.counts.get_mut()));
+        self.part_start = Instant::now();
+        let result = result.into();
+        let separator = if let Answer::Art(_) = result {
+            "\n"
+        } else {
+            " "
+        };
+        writeln!(
+            self.output,
+            "Part {} result:{separator}{result}",
+            self.results.len() + 1,
+        )
+        .unwrap();
+        self.results.push(result);
     }
-}
 
-/// Helper that resolves to `println!()`, disabled in release mode
-#[macro_export]
-macro_rules! debug {
-    ($($e:expr),+) => {
-        {
-            #[cfg(debug_assertions)]
-            {
-                println!($($e),+)
-            }
+    /// Runs a parsing stage on the input, panicking with the error if the input is malformed
+    pub fn parse<T>(&self, parse: impl FnOnce(&'a str) -> Result<T, ParseError>) -> T {
+        parse(self.input).unwrap_or_else(|err| panic!("invalid input: {err}"))
+    }
+
+    /// Lines of the input with their 1-based line numbers. Streaming days read the input file one line at a time
+    /// through it, in constant memory; since the file is not kept, the input can only be read once.
+    pub fn lines(&mut self) -> InputLines<'a> {
+        self.lines
+            .take()
+            .expect("the input can only be read once through `context.lines()`")
+    }
+
+    /// Parses each line of the input with [`Context::lines`], panicking with the error if a line is malformed
+    pub fn parse_lines<T>(
+        &mut self,
+        mut parse: impl FnMut(usize, &str) -> Result<T, ParseError> + 'a,
+    ) -> impl Iterator<Item = T> + 'a {
+        self.lines().map(move |(number, line)| {
+            parse(number, &line).unwrap_or_else(|err| panic!("invalid input: {err}"))
+        })
+    }
+
+    /// Whether messages of the given level are output for this day; prefer the [`trace!`], [`debug!`] and [`info!`] macros
+    pub fn log_enabled(&self, level: Level) -> bool {
+        self.logger.enabled(level)
+    }
+
+    /// Outputs a log message; prefer the [`trace!`], [`debug!`] and [`info!`] macros
+    pub fn log(&self, level: Level, args: Arguments) {
+        self.logger.log(level, args);
+    }
+
+    /// Reports that `done` out of `total` items (e.g. machines or areas) of the current part are processed.
+    /// The runner shows it as a live progress line when running in a terminal, and ignores it otherwise.
+    pub fn progress(&self, done: usize, total: usize) {
+        self.progress.update(self.results.len() + 1, done, total);
+    }
+
+    /// Increments the named operation counter of the current part, e.g. `context.count("backtrack_calls")`.
+    /// The runner reports every counter next to the part's answer.
+    pub fn count(&self, name: &'static str) {
+        self.count_by(name, 1);
+    }
+
+    /// Adds `amount` to the named operation counter of the current part
+    pub fn count_by(&self, name: &'static str, amount: u64) {
+        self.counts.borrow_mut().add(name, amount);
+    }
+
+    /// Current value of the named operation counter of the current part
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counts.borrow().get(name)
+    }
+
+    /// Whether the runner gave up on this day (see [`RunOptions::timeout`]).
+    /// Long-running solutions should check this periodically and return early once it's set.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Value of a tunable parameter: the override from the config file if there is one, otherwise `default`
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> T
+    where
+        T::Err: Display,
+    {
+        match self.params.and_then(|params| params.get(name)) {
+            Some(value) => value.parse().unwrap_or_else(|err| {
+                panic!("invalid value {value:?} for parameter {name}: {err}")
+            }),
+            None => default,
         }
-    };
+    }
+
+    /// Whether visualization frames are being saved for this run
+    pub fn visualize_enabled(&self) -> bool {
+        self.visualizer.enabled()
+    }
+
+    /// Saves a visualization frame. `frame` is only called if visualization is enabled,
+    /// so expensive rendering can be done inside it. Rendering counts towards the part whose result comes next, so
+    /// frames of a part should be saved before its result.
+    pub fn visualize(&self, name: &str, frame: impl FnOnce() -> Frame) {
+        if !self.visualizer.enabled() {
+            return;
+        }
+        if let Some(path) = self.visualizer.save(name, frame()) {
+            info!(self, "saved frame {}", path.display());
+        }
+    }
 }
 
-/// Helper that resolves to `println!()`, disabled in release mode and when running on full data
-#[macro_export]
-macro_rules! debug_example {
-    ($context:expr, $($e:expr),+) => {
-        {
-            #[cfg(debug_assertions)]
-            {
-                if ($context.run_type == crate::RunType::Examples) {
-                    println!($($e),+)
-                }
-            }
+/// Numbers of the days that are part of this build, see the `day-<n>` cargo features
+pub fn day_numbers() -> Vec<u8> {
+    days::enabled().map(|(day_num, _)| day_num).collect()
+}
+
+/// Solution of a day, panicking if there is no such day or it was left out of this build
+fn day_info(day: u8) -> &'static DayInfo {
+    days::get(day).unwrap_or_else(|| {
+        if day == 0 || day as usize > DAYS.len() {
+            panic!("day {} not found, max day is {}", day, DAYS.len())
         }
-    };
+        panic!("day {day} is not part of this build, enable the day-{day} feature")
+    })
 }
 
 /// Runs the solution for the given 1-indexed day number (1-25).
 /// * `day` - day number (1-25)
 /// * `part` - which part to run (1, 2 or both - 3)
 /// * `run_type` - whether to run on the example code or full input
-pub fn run_single(day: u8, part: Part, run_type: RunType) {
-    if day == 0 {
-        panic!("day cannot be 0")
-    }
-    if let Some(day_info) = DAYS.get(day as usize - 1) {
-        println!("\nRunning single day {}: {}\n\n", day, day_info.name);
-        match run_type {
-            Examples => run_examples(day_info, part),
-            Full => run_full(day, day_info, part),
+/// * `options` - options shared by all days
+pub fn run_single(day: u8, part: Part, run_type: RunType, options: &RunOptions) {
+    day_info(day);
+    let jobs = day_jobs(day, part, run_type, options, open_log_sink(options));
+    let mut report = Report::new(options);
+    report.header();
+    let mut failure = None;
+    for job in &jobs {
+        let outcome = run_day_guarded(job);
+        report.day(job, &outcome);
+        match outcome {
+            DayOutcome::Done(_) => {}
+            DayOutcome::Failed(message) => {
+                failure.get_or_insert(format!("day {day} failed: {message}"));
+            }
+            DayOutcome::TimedOut => {
+                failure.get_or_insert(format!("day {day} timed out"));
+            }
         }
-    } else {
-        panic!("day {} not found, max day is {}", day, DAYS.len())
+    }
+    report.total(None);
+    if let Some(failure) = failure {
+        panic!("{failure}")
     }
 }
 
 /// Runs the solution for all days.
 /// * `run_type` - whether to run on the example code or full input
-pub fn run_all(run_type: RunType) {
-    println!("\nRunning all days\n\n");
-    for (day, day_info) in DAYS.iter().enumerate() {
-        let day = (day + 1) as u8;
-        println!("# Day {}: {}", day, day_info.name);
-        match run_type {
-            Examples => run_examples(day_info, Both),
-            Full => run_full(day, day_info, Both),
+/// * `options` - options shared by all days
+pub fn run_all(run_type: RunType, options: &RunOptions) {
+    let log_sink = open_log_sink(options);
+    let jobs = days::enabled()
+        .flat_map(|(day, _)| day_jobs(day, Both, run_type, options, log_sink.clone()))
+        .collect::<Vec<_>>();
+    let time = Instant::now();
+    let mut report = Report::new(options);
+    report.header();
+    let mut failed = Vec::new();
+    let mut record = |job: &DayJob, outcome: DayOutcome| {
+        report.day(job, &outcome);
+        if let DayOutcome::Failed(_) = outcome {
+            failed.push(job.day_num);
         }
+    };
+
+    if options.jobs <= 1 {
+        for job in &jobs {
+            let outcome = run_day_guarded(job);
+            record(job, outcome);
+        }
+    } else {
+        // Each day writes its logs into its own buffer, which is printed once all previous days
+        // are done, so the output looks the same as a sequential run
+        parallel::run_ordered(
+            options.jobs,
+            &jobs,
+            |job| {
+                let log_buffer = Arc::new(Mutex::new(Vec::new()));
+                let job = DayJob {
+                    log_sink: job.log_sink.as_ref().map(|_| log_buffer.clone() as LogSink),
+                    ..job.clone()
+                };
+                let outcome = run_day_guarded(&job);
+                let logs = take(&mut *log_buffer.lock().unwrap());
+                (logs, outcome)
+            },
+            |job, (logs, outcome)| {
+                if let Some(sink) = &log_sink {
+                    sink.lock().unwrap().write_all(&logs).unwrap();
+                }
+                record(job, outcome);
+            },
+        );
+    }
+
+    report.total(Some(time.elapsed()));
+    if !failed.is_empty() {
+        panic!("days {failed:?} failed")
     }
 }
 
-fn run_day(context: &mut Context) {
-    match context.part {
-        One => println!("### Running part 1"),
-        Both => println!("### Running both parts"),
+/// Prints the full-run history of the given day, highlighting runs where answers, source code or runtime changed.
+/// * `day` - day number (1-25)
+pub fn show_history(day: u8, options: &RunOptions) {
+    let info = day_info(day);
+    let entries = history::load(&options.day_dir(day))
+        .unwrap_or_else(|err| panic!("could not load history: {err}"));
+    println!("\nHistory of day {}: {}\n", day, info.name);
+    if entries.is_empty() {
+        println!("No full runs recorded yet");
+        return;
+    }
+    let current_hash = history::source_hash(day);
+    for (entry, changes) in history::changes(&entries) {
+        if !changes.any() {
+            continue;
+        }
+        let mut notes = Vec::new();
+        if changes.first_run {
+            notes.push("first run");
+        }
+        if changes.answers {
+            notes.push("answers changed");
+        }
+        if changes.source {
+            notes.push("source changed");
+        }
+        if changes.runtime {
+            notes.push("runtime changed");
+        }
+        println!(
+            "{}  part {}  source {:016x}{}  {:>12.3?}  [{}]  {}",
+            Timestamp(entry.timestamp),
+            match entry.part {
+                One => "1",
+                Both => "*",
+            },
+            entry.source_hash,
+            if entry.source_hash == current_hash {
+                " (current)"
+            } else {
+                ""
+            },
+            entry.elapsed,
+            notes.join(", "),
+            entry
+                .answers
+                .iter()
+                .map(Answer::to_string)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        );
+    }
+    println!("\n{} runs recorded in total", entries.len());
+}
+
+/// Submits the answer to a level (part) of a day, as given by the latest full run that produced it
+pub fn submit_answer(day: u8, level: u8, options: &RunOptions) {
+    day_info(day);
+    if !(1..=2).contains(&level) {
+        panic!("invalid level {level}, must be 1 or 2")
+    }
+    let day_dir = options.day_dir(day);
+    let entries =
+        history::load(&day_dir).unwrap_or_else(|err| panic!("could not load history: {err}"));
+    let Some(entry) = entries
+        .iter()
+        .rev()
+        .find(|entry| entry.answers.len() >= level as usize)
+    else {
+        println!("No full run of day {day} has produced an answer to part {level} yet");
+        return;
+    };
+    let answer = &entry.answers[level as usize - 1];
+    if !answer.is_submittable() {
+        println!(
+            "The answer to day {day} part {level} can't be submitted automatically:\n{answer}"
+        );
+        return;
+    }
+    println!(
+        "Submitting {answer} for day {day} part {level} (from the run at {}{})",
+        Timestamp(entry.timestamp),
+        if entry.source_hash == history::source_hash(day) {
+            ""
+        } else {
+            ", source changed since"
+        }
+    );
+    let InputProvider { server, backend } = &options.inputs;
+    match submit::submit(
+        &**backend,
+        server,
+        &day_dir,
+        day,
+        level,
+        &answer.to_string(),
+    ) {
+        Ok(verdict) => println!("Verdict: {verdict}"),
+        Err(err) => println!("{err}"),
+    }
+}
+
+/// Runs the main solution and all alternative solutions of a day on the same input,
+/// checking that their answers agree and printing their timings side by side.
+/// Examples mode uses the built-in example.
+pub fn compare(day: u8, run_type: RunType, options: &RunOptions) {
+    let info = day_info(day);
+    println!("\nDay {day}: {}\n", info.name);
+    if info.variants.is_empty() {
+        println!("No alternative solutions to compare");
+        return;
+    }
+    let job = DayJob {
+        day_num: day,
+        day: info,
+        part: Both,
+        run_type,
+        options: options.clone(),
+        log_sink: open_log_sink(options),
+        example: None,
+        variant: None,
+    };
+    let variants = once(("main", None)).chain(
+        info.variants
+            .iter()
+            .map(|variant| (variant.name(), Some(*variant))),
+    );
+    println!(
+        "{:<20}  {:<20}  {:<20}  {:>10}  {:>10}  {:>10}",
+        "variant", "part 1", "part 2", "time 1", "time 2", "total"
+    );
+    let mut reference: Option<(&str, Vec<Answer>)> = None;
+    let mut problems = Vec::new();
+    for (name, variant) in variants {
+        let outcome = run_day_guarded(&DayJob {
+            variant,
+            ..job.clone()
+        });
+        let run = match outcome {
+            DayOutcome::Done(run) => run,
+            DayOutcome::Failed(message) => {
+                println!("{name:<20}  failed: {message}");
+                problems.push(format!("{name} failed"));
+                continue;
+            }
+            DayOutcome::TimedOut => {
+                println!("{name:<20}  timed out");
+                problems.push(format!("{name} timed out"));
+                continue;
+            }
+        };
+        let answer = |part: usize| {
+            run.answers
+                .get(part)
+                .map_or(String::new(), |answer| report::shorten(&answer.to_string()))
+        };
+        let time = |part: usize| {
+            run.part_times
+                .get(part)
+                .map_or(String::new(), |time| HumanDuration(*time).to_string())
+        };
+        println!(
+            "{name:<20}  {:<20}  {:<20}  {:>10}  {:>10}  {:>10}",
+            answer(0),
+            answer(1),
+            time(0),
+            time(1),
+            HumanDuration(run.elapsed).to_string()
+        );
+        match &reference {
+            None => reference = Some((name, run.answers)),
+            Some((reference, answers)) if *answers != run.answers => {
+                problems.push(format!("{name} disagrees with {reference}"))
+            }
+            Some(_) => {}
+        }
+    }
+    println!();
+    if problems.is_empty() {
+        println!("All {} solutions agree", info.variants.len() + 1);
+    } else {
+        panic!("{}", problems.join(", "))
+    }
+}
+
+/// Prints the shape of a day's input (or built-in example), and whether the day's parser accepts it
+pub fn inspect_input(day: u8, run_type: RunType, options: &RunOptions) {
+    let info = day_info(day);
+    let input = match run_type {
+        Examples => info.example.to_string(),
+        Full => options
+            .inputs
+            .load(&options.day_dir(day), day)
+            .unwrap_or_else(|err| panic!("could not load input of day {day}: {err}")),
+    };
+    println!("\nInput of day {day}: {}\n", info.name);
+    print!("{}", InputStats::of(&input));
+    match (info.parse)(&input) {
+        Ok(()) => println!("\nAccepted by the parser"),
+        Err(err) => println!("\nRejected by the parser: {err}"),
+    }
+    if let Some(rules) = info.validator {
+        let violations = validate::validate(rules, &input);
+        println!("{} violations of the input format", violations.len());
+        for violation in violations {
+            println!("  {violation}");
+        }
+    }
+}
+
+fn viz_dir(job: &DayJob) -> Option<PathBuf> {
+    job.options.visualize.then(|| {
+        let dir = job.options.day_dir(job.day_num).join("viz");
+        match (job.run_type, &job.example) {
+            (Examples, None) => dir.join("examples"),
+            // Keep the frames of each example file apart
+            (Examples, Some(example)) => dir.join("examples").join(&example.name),
+            (Full, _) => dir.join("full"),
+        }
+    })
+}
+
+/// Jobs for running a day: just one for full runs, and one per example (built-in first) in examples mode
+fn day_jobs(
+    day_num: u8,
+    part: Part,
+    run_type: RunType,
+    options: &RunOptions,
+    log_sink: Option<LogSink>,
+) -> Vec<DayJob> {
+    let job = DayJob {
+        day_num,
+        day: day_info(day_num),
+        part,
+        run_type,
+        options: options.clone(),
+        log_sink,
+        example: None,
+        variant: None,
+    };
+    if run_type == Full {
+        return vec![job];
+    }
+    let files = examples::load(&options.day_dir(day_num))
+        .unwrap_or_else(|err| panic!("could not load examples of day {day_num}: {err}"));
+    let extra = files.into_iter().map(|file| DayJob {
+        example: Some(Arc::new(file)),
+        ..job.clone()
+    });
+    once(job.clone()).chain(extra).collect()
+}
+
+fn open_log_sink(options: &RunOptions) -> Option<LogSink> {
+    options
+        .log
+        .open_sink()
+        .unwrap_or_else(|err| panic!("could not open log file: {err}"))
+}
+
+/// Everything needed to run a single day
+#[derive(Clone)]
+struct DayJob {
+    day_num: u8,
+    day: &'static DayInfo,
+    part: Part,
+    run_type: RunType,
+    options: RunOptions,
+    log_sink: Option<LogSink>,
+    /// Example file to use instead of the built-in example
+    example: Option<Arc<ExampleFile>>,
+    /// Alternative solution to run instead of the day's main one
+    variant: Option<&'static dyn Variant>,
+}
+
+/// Outcome of running a single day
+struct DayRun {
+    answers: Vec<Answer>,
+    elapsed: Duration,
+    /// CPU time of the thread running the solution, where it can be measured; threads the solution spawns itself
+    /// are not counted
+    cpu_time: Option<Duration>,
+    /// Time between the start of the run or the previous part's result and each part's result. Like `memory` and
+    /// `counts`, it is meaningless for days computing both parts together before giving the first result
+    part_times: Vec<Duration>,
+    /// Heap usage of each part, only collected with the `memory-stats` feature
+    memory: Vec<MemoryStats>,
+    /// Operation counters of each part, see [`Context::count`]
+    counts: Vec<Counts>,
+}
+
+/// How a day run ended
+enum DayOutcome {
+    Done(DayRun),
+    Failed(String),
+    /// Exceeded [`RunOptions::timeout`]; the day was asked to cancel, but may still be running in the background
+    TimedOut,
+}
+
+/// Runs a day, catching panics and enforcing the time budget, if any
+fn run_day_guarded(job: &DayJob) -> DayOutcome {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let Some(timeout) = job.options.timeout_for(job.day_num) else {
+        return match parallel::catch_panic(|| run_day(job, cancelled)) {
+            Ok(run) => DayOutcome::Done(run),
+            Err(message) => DayOutcome::Failed(message),
+        };
+    };
+
+    // Run on a separate thread that we can stop waiting for
+    let (sender, receiver) = channel();
+    {
+        let job = job.clone();
+        let cancelled = cancelled.clone();
+        thread::spawn(move || {
+            let result = parallel::catch_panic(|| run_day(&job, cancelled));
+            // The runner may have stopped waiting already
+            let _ = sender.send(result);
+        });
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(run)) => DayOutcome::Done(run),
+        Ok(Err(message)) => DayOutcome::Failed(message),
+        Err(RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::Relaxed);
+            DayOutcome::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => unreachable!("day thread exited without a result"),
+    }
+}
+
+fn run_day(job: &DayJob, cancelled: Arc<AtomicBool>) -> DayRun {
+    match job.run_type {
+        Examples => run_examples(job, cancelled),
+        Full => run_full(job, cancelled),
+    }
+}
+
+/// Runs both parts of a day on the given input, without any output, returning the answers
+#[cfg(test)]
+pub(crate) fn solve(day_num: u8, input: &str) -> Vec<Answer> {
+    solve_with(day_num, input, None)
+}
+
+/// Like [`solve`], but using the given alternative solution instead of the main one
+#[cfg(test)]
+pub(crate) fn solve_with(day_num: u8, input: &str, variant: Option<&dyn Variant>) -> Vec<Answer> {
+    let mut output = sink();
+    let mut context = Context::new(input, day_num, day_info(day_num), Full, Both, &mut output);
+    run_solution(&mut context, variant).answers
+}
+
+fn run_solution(context: &mut Context, variant: Option<&dyn Variant>) -> DayRun {
+    // Inputs read line by line while running aren't loaded to be checked beforehand
+    let streamed = matches!(context.lines, Some(InputLines::Reader { .. }));
+    if let Some(rules) = context.day.validator
+        && !streamed
+    {
+        let violations = validate::validate(rules, context.input);
+        if !violations.is_empty() {
+            let violations = violations
+                .iter()
+                .map(|violation| format!("\n  {violation}"));
+            panic!("invalid input:{}", violations.collect::<String>())
+        }
     }
     let time = Instant::now();
-    (context.day.run)(context);
-    println!("### done in {:?}\n", time.elapsed());
+    let cpu_start = parallel::thread_cpu_time();
+    context.memory_tracker = MemoryTracker::start();
+    context.part_start = time;
+    match variant {
+        Some(variant) => variant.run(context),
+        None => (context.day.run)(context),
+    }
+    let elapsed = time.elapsed();
+    let cpu_time = cpu_start
+        .zip(parallel::thread_cpu_time())
+        .map(|(start, end)| end.saturating_sub(start));
+    context.logger.flush();
     match context.part {
-        One | Both if context.result_count == 0 => {
+        // A cancelled solution may stop without giving all results, nobody is waiting for them anyway
+        _ if context.is_cancelled() => {}
+        One | Both if context.results.is_empty() => {
             panic!("context.result() must be called to output your final result")
         }
-        Both if context.result_count == 1 => panic!(
+        Both if context.results.len() == 1 => panic!(
             "context.result() must be called twice then context.part == Both, once for each part's output"
         ),
         _ => {}
     }
+    DayRun {
+        answers: context.results.clone(),
+        elapsed,
+        cpu_time,
+        part_times: context.part_times.clone(),
+        memory: context.memory.clone(),
+        counts: context.part_counts.clone(),
+    }
 }
 
-fn run_examples(day: &DayInfo, part: Part) {
-    let mut output = stdout();
+fn run_examples(job: &DayJob, cancelled: Arc<AtomicBool>) -> DayRun {
+    // Results are only shown in the report
+    let mut output = sink();
+    let input = job
+        .example
+        .as_ref()
+        .map_or(job.day.example, |example| &example.input);
     let mut context = Context {
-        input: day.example,
-        run_type: Examples,
-        part,
-        day,
-        result_count: 0,
-        output: &mut output,
+        logger: Logger::new(job.day_num, &job.options.log, job.log_sink.clone()),
+        visualizer: Visualizer::new(viz_dir(job)),
+        cancelled,
+        params: job.options.days.get(&job.day_num).map(|day| &day.params),
+        progress: Progress::new(job.day_num, job.options.progress_enabled()),
+        ..Context::new(input, job.day_num, job.day, Examples, job.part, &mut output)
     };
-    run_day(&mut context);
+    run_solution(&mut context, job.variant)
 }
 
-fn run_full(day_num: u8, day: &DayInfo, part: Part) {
+fn run_full(job: &DayJob, cancelled: Arc<AtomicBool>) -> DayRun {
     // Files: data/{day}/{input,output}.txt
-    let path = Path::new("data").join(day_num.to_string());
-    // Read input file
-    let mut input = String::new();
-    File::open(path.join("input.txt"))
-        .unwrap()
-        .read_to_string(&mut input)
-        .unwrap();
-    // Open output file
-    let output_file = File::create(path.join("output.txt")).unwrap();
-    let mut output = TeeWriter::new(output_file, stdout());
+    let path = job.options.day_dir(job.day_num);
+    // Read input file, downloading it the first time; streaming days read it line by line instead.
+    // Alternative solutions parse the whole input, so they always get it in memory.
+    let inputs = &job.options.inputs;
+    let loaded = if job.day.streaming && job.variant.is_none() {
+        let reader = inputs.open(&path, job.day_num);
+        reader.map(|reader| (String::new(), Some(Box::new(reader) as Box<dyn BufRead>)))
+    } else {
+        let input = inputs.load(&path, job.day_num);
+        input.map(|input| (input, None))
+    };
+    let (input, reader) =
+        loaded.unwrap_or_else(|err| panic!("could not load input of day {}: {err}", job.day_num));
+    // Open output file; alternative solutions leave the output and history to the main one
+    let mut output: Box<dyn Write> = match job.variant {
+        Some(_) => Box::new(sink()),
+        None => Box::new(File::create(path.join("output.txt")).unwrap()),
+    };
+    let lines = match reader {
+        Some(reader) => InputLines::from_reader(reader),
+        None => InputLines::new(&input),
+    };
     // Create context
     let mut context = Context {
-        input: &input,
-        run_type: Full,
-        part,
-        day,
-        result_count: 0,
-        output: &mut output,
+        logger: Logger::new(job.day_num, &job.options.log, job.log_sink.clone()),
+        visualizer: Visualizer::new(viz_dir(job)),
+        cancelled,
+        params: job.options.days.get(&job.day_num).map(|day| &day.params),
+        lines: Some(lines),
+        progress: Progress::new(job.day_num, job.options.progress_enabled()),
+        ..Context::new(&input, job.day_num, job.day, Full, job.part, &mut output)
     };
-    run_day(&mut context);
+    let run = run_solution(&mut context, job.variant);
+    if context.is_cancelled() || job.variant.is_some() {
+        // Don't record partial results
+        return run;
+    }
+    let entry = HistoryEntry::now(job.day_num, job.part, run.elapsed, run.answers.clone());
+    history::append(&path, &entry).unwrap_or_else(|err| panic!("could not save history: {err}"));
+    run
 }
@@ -0,0 +1,166 @@
+//! Leveled debug output for solutions, see [`trace!`](crate::trace), [`debug!`](crate::debug) and [`info!`](crate::info).
+//!
+//! Logging is configured per run through [`LogOptions`], and works the same in debug and release builds.
+//! When a message's level is disabled its arguments are never formatted, so leaving log calls in hot loops is cheap.
+
+use std::fmt::{Arguments, Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write, stderr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub enum Level {
+    /// Very detailed output, e.g. once per input line or loop iteration
+    Trace,
+    /// Intermediate results, a few lines per part
+    Debug,
+    /// High-level progress information
+    Info,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown log level \"{0}\", expected one of: trace, debug, info")]
+pub struct ParseLevelError(String);
+
+impl FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(Level::Trace),
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            _ => Err(ParseLevelError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LogTarget {
+    #[default]
+    Stderr,
+    /// Log file, truncated at the start of each run
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Minimum level to output, `None` disables logging entirely
+    pub level: Option<Level>,
+    /// Days to output logs for; all days if empty
+    pub days: Vec<u8>,
+    pub target: LogTarget,
+}
+
+pub(crate) type LogSink = Arc<Mutex<dyn Write + Send>>;
+
+impl LogOptions {
+    /// Minimum level enabled for the given day, if any
+    pub(crate) fn level_for(&self, day: u8) -> Option<Level> {
+        if self.days.is_empty() || self.days.contains(&day) {
+            self.level
+        } else {
+            None
+        }
+    }
+
+    /// Opens the log target; should be done once per run, so all days share the same sink
+    pub(crate) fn open_sink(&self) -> std::io::Result<Option<LogSink>> {
+        if self.level.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(match &self.target {
+            LogTarget::Stderr => Arc::new(Mutex::new(stderr())),
+            LogTarget::File(path) => Arc::new(Mutex::new(BufWriter::new(File::create(path)?))),
+        }))
+    }
+}
+
+/// Per-day logger, owned by the [`Context`](crate::Context)
+pub(crate) struct Logger {
+    day: u8,
+    level: Option<Level>,
+    sink: Option<LogSink>,
+}
+
+impl Logger {
+    pub(crate) fn new(day: u8, options: &LogOptions, sink: Option<LogSink>) -> Self {
+        Self {
+            day,
+            level: options.level_for(day).filter(|_| sink.is_some()),
+            sink,
+        }
+    }
+
+    pub(crate) fn enabled(&self, level: Level) -> bool {
+        self.level.is_some_and(|min_level| level >= min_level)
+    }
+
+    pub(crate) fn log(&self, level: Level, args: Arguments) {
+        if !self.enabled(level) {
+            return;
+        }
+        if let Some(sink) = &self.sink {
+            let mut sink = sink.lock().unwrap();
+            writeln!(sink, "[day {:>2}] {level:<5} {args}", self.day).unwrap();
+        }
+    }
+
+    pub(crate) fn flush(&self) {
+        if let Some(sink) = &self.sink {
+            sink.lock().unwrap().flush().unwrap();
+        }
+    }
+}
+
+/// Outputs a message at the given [`Level`] through the context's logger.
+/// Arguments are only evaluated if the level is enabled for the current day.
+#[macro_export]
+macro_rules! log {
+    ($context:expr, $level:expr, $($arg:tt)+) => {
+        {
+            let context: &$crate::Context = &$context;
+            let level: $crate::logging::Level = $level;
+            if context.log_enabled(level) {
+                context.log(level, format_args!($($arg)+));
+            }
+        }
+    };
+}
+
+/// Logs very detailed output, e.g. once per input line or loop iteration
+#[macro_export]
+macro_rules! trace {
+    ($context:expr, $($arg:tt)+) => {
+        $crate::log!($context, $crate::logging::Level::Trace, $($arg)+)
+    };
+}
+
+/// Logs intermediate results
+#[macro_export]
+macro_rules! debug {
+    ($context:expr, $($arg:tt)+) => {
+        $crate::log!($context, $crate::logging::Level::Debug, $($arg)+)
+    };
+}
+
+/// Logs high-level progress information
+#[macro_export]
+macro_rules! info {
+    ($context:expr, $($arg:tt)+) => {
+        $crate::log!($context, $crate::logging::Level::Info, $($arg)+)
+    };
+}
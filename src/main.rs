@@ -1,41 +1,148 @@
 use aoc_2025_rust::RunType::{Examples, Full};
+use aoc_2025_rust::config::{self, Config};
+use aoc_2025_rust::logging::LogTarget;
 use aoc_2025_rust::*;
-use std::io;
 use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, io, panic};
 
 fn main() {
-    println!("Advent of Code 2023: Rust");
-    println!("Enter which day/part you would like to run, and in which mode (full/examples).");
-    println!("- full mode - run using input/output files");
-    println!("- examples mode - run using example input, outputting to stdout");
-    println!();
-    println!("Format: [<day>] [<mode>]");
-    println!("  day - day number (1-25), or \"all\" (default)");
-    println!("  mode - one of: f, f1, e, e1 (defaults to f, only f/e allowed if day is \"all\"):");
-    println!("    - f1/f - run part(s) 1/both in full mode");
-    println!("    - e1/e - run part(s) 1/both in examples mode");
-
-    let line = io::stdin().lock().lines().next().unwrap().unwrap();
-    if line.trim().is_empty() {
-        run_all(Full);
-        return;
+    let mut words = env::args().skip(1).collect::<Vec<_>>();
+    if words.is_empty() {
+        print_help();
+        let line = io::stdin().lock().lines().next().unwrap().unwrap();
+        words = line.split_whitespace().map(str::to_string).collect();
     }
 
-    let mut words = line.split_whitespace();
+    // Settings are applied from lowest to highest precedence: config file, environment, flags
+    let config = Config::load(&config_path(&words))
+        .unwrap_or_else(|err| panic!("could not load config: {err}"));
+    let default_mode = match config.run_type {
+        Some(Examples) => "e",
+        Some(Full) | None => "f",
+    };
+    let mut options = RunOptions::default();
+    config.apply(&mut options);
+    options.inputs.server.apply_env();
+    let words = parse_flags(words, &mut options);
+    let mut words = words.iter().map(String::as_str);
 
     let day = match words.next() {
-        None | Some("all") => {
-            run_all(match words.next().unwrap_or("f") {
+        Some("history") => {
+            show_history(
+                words.next().expect("missing day").parse::<u8>().unwrap(),
+                &options,
+            );
+            return;
+        }
+        Some("submit") => {
+            let day = words.next().expect("missing day").parse::<u8>().unwrap();
+            let level = words.next().expect("missing part").parse::<u8>().unwrap();
+            submit_answer(day, level, &options);
+            return;
+        }
+        Some("inspect") => {
+            let day = words.next().expect("missing day").parse::<u8>().unwrap();
+            let run_type = match words.next().unwrap_or("f") {
+                "f" => Full,
+                "e" => Examples,
+                _ => panic!("invalid mode"),
+            };
+            inspect_input(day, run_type, &options);
+            return;
+        }
+        Some("compare") => {
+            let day = words.next().expect("missing day").parse::<u8>().unwrap();
+            let run_type = match words.next().unwrap_or(default_mode) {
                 "f" => Full,
                 "e" => Examples,
                 _ => panic!("invalid mode"),
-            });
+            };
+            compare(day, run_type, &options);
+            return;
+        }
+        Some("fuzz") => {
+            let days = match words.next().expect("missing day") {
+                "all" => day_numbers(),
+                day => vec![day.parse::<u8>().unwrap()],
+            };
+            let (mut iterations, mut seed) = (10_000, None);
+            while let Some(flag) = words.next() {
+                let value = words.next().expect("missing value");
+                match flag {
+                    "--iterations" => iterations = value.parse().unwrap(),
+                    "--seed" => seed = Some(value.parse().unwrap()),
+                    _ => panic!("unexpected argument {flag}"),
+                }
+            }
+            let seed = seed.unwrap_or_else(time_seed);
+            // Panics are expected while minimizing a crash, and reported below
+            panic::set_hook(Box::new(|_| {}));
+            let mut crashed = Vec::new();
+            for day in days {
+                match fuzz::fuzz(day, iterations, seed) {
+                    Ok(stats) => println!(
+                        "Day {day}: {} inputs accepted, {} rejected, corpus of {}",
+                        stats.accepted, stats.rejected, stats.corpus
+                    ),
+                    Err(crash) => {
+                        println!(
+                            "Day {day}: parser panicked: {}\nMinimized input:\n{}\n",
+                            crash.message, crash.input
+                        );
+                        crashed.push(day);
+                    }
+                }
+            }
+            if !crashed.is_empty() {
+                let _ = panic::take_hook();
+                panic!("parsers of days {crashed:?} panicked")
+            }
+            return;
+        }
+        Some("gen") => {
+            let day = words.next().expect("missing day").parse::<u8>().unwrap();
+            let (mut size, mut seed) = (1000, None);
+            while let Some(flag) = words.next() {
+                let value = words.next().expect("missing value");
+                match flag {
+                    "--size" => size = value.parse().unwrap(),
+                    "--seed" => seed = Some(value.parse().unwrap()),
+                    _ => panic!("unexpected argument {flag}"),
+                }
+            }
+            let seed = seed.unwrap_or_else(time_seed);
+            let input = generate::generate(day, size, seed)
+                .unwrap_or_else(|| panic!("day {day} has no input generator"));
+            println!("{input}");
+            return;
+        }
+        Some("serve") => {
+            let port = match (words.next(), words.next()) {
+                (Some("--port"), Some(port)) => port.parse().unwrap(),
+                (None, _) => 8025,
+                (Some(flag), _) => panic!("unexpected argument {flag}"),
+            };
+            serve::serve(port, &options)
+                .unwrap_or_else(|err| panic!("could not start the dashboard: {err}"));
+            return;
+        }
+        None | Some("all") => {
+            run_all(
+                match words.next().unwrap_or(default_mode) {
+                    "f" => Full,
+                    "e" => Examples,
+                    _ => panic!("invalid mode"),
+                },
+                &options,
+            );
             return;
         }
         Some(day) => day.parse::<u8>().unwrap(),
     };
 
-    let (part, run_type) = match words.next().unwrap_or("f") {
+    let (part, run_type) = match words.next().unwrap_or(default_mode) {
         "f" => (Part::Both, Full),
         "f1" => (Part::One, Full),
         "e" => (Part::Both, Examples),
@@ -43,5 +150,137 @@ fn main() {
         _ => panic!("invalid mode"),
     };
 
-    run_single(day, part, run_type);
+    run_single(day, part, run_type, &options);
+}
+
+fn print_help() {
+    println!("Advent of Code 2023: Rust");
+    println!("Enter which day/part you would like to run, and in which mode (full/examples).");
+    println!("- full mode - run using input/output files");
+    println!("- examples mode - run using example input, outputting to stdout");
+    println!();
+    println!("Format: [<options>] [<day>] [<mode>]");
+    println!("  day - day number (1-25), or \"all\" (default)");
+    println!(
+        "  mode - one of: f, f1, e, e1 (defaults to f or run_type in aoc.toml, only f/e allowed if day is \"all\"):"
+    );
+    println!("    - f1/f - run part(s) 1/both in full mode");
+    println!("    - e1/e - run part(s) 1/both in examples mode");
+    println!("Other commands:");
+    println!(
+        "  history <day> - show when a day's answers, source or runtime changed across full runs"
+    );
+    println!(
+        "  submit <day> <part> - submit the answer of the latest full run (session token from AOC_SESSION)"
+    );
+    println!(
+        "  inspect <day> [f|e] - show the shape of a day's input and whether its parser accepts it"
+    );
+    println!(
+        "  compare <day> [f|e] - run all solutions of a day on the same input and compare answers and timings"
+    );
+    println!(
+        "  gen <day> [--size <n>] [--seed <seed>] - print a random input for stress tests (size defaults to 1000)"
+    );
+    println!(
+        "  fuzz <day|all> [--iterations <n>] [--seed <seed>] - check that parsers reject mutated inputs without panicking"
+    );
+    println!(
+        "  serve [--port <port>] - start a local web dashboard with answers, timings and frames (port defaults to 8025)"
+    );
+    println!("  options:");
+    println!(
+        "    --log <level> - output solution logs of this level or above (trace, debug, info)"
+    );
+    println!("    --log-days <days> - only output logs for these days, comma-separated");
+    println!("    --log-file <path> - write logs to this file instead of stderr");
+    println!("    --viz - save visualization frames under data/<day>/viz/");
+    println!(
+        "    --timeout <seconds> - stop waiting for a day after this long and mark it as timed out"
+    );
+    println!(
+        "    --jobs <n>, -j <n> - run up to n days concurrently when running all days (0 = one per CPU)"
+    );
+    println!("    --config <path> - read settings from this file instead of aoc.toml");
+    println!("    --data-dir <path> - directory with the files of each day (default: data)");
+    println!("    --year <year> - puzzle year to download inputs and submit answers for");
+    println!("    --format <text|json> - print human-readable text, or one JSON object per day");
+    println!("    --color, --no-color - force colors on or off (default: only in a terminal)");
+    println!();
+    println!("The same arguments can also be passed on the command line.");
+}
+
+/// Applies all `--flag [value]` options, returning the remaining positional arguments
+fn parse_flags(words: Vec<String>, options: &mut RunOptions) -> Vec<String> {
+    let mut positional = Vec::new();
+    let mut words = words.into_iter();
+    while let Some(word) = words.next() {
+        let mut value = || {
+            words
+                .next()
+                .unwrap_or_else(|| panic!("missing value for {word}"))
+        };
+        match word.as_str() {
+            "--log" => options.log.level = Some(value().parse().unwrap()),
+            "--log-days" => {
+                options.log.days = value()
+                    .split(',')
+                    .map(|day| day.parse::<u8>().unwrap())
+                    .collect()
+            }
+            "--log-file" => options.log.target = LogTarget::File(value().into()),
+            "--viz" => options.visualize = true,
+            "--timeout" => {
+                options.timeout = Some(Duration::from_secs_f64(value().parse().unwrap()));
+                // Also replaces the per-day timeouts from the config file
+                options.days.values_mut().for_each(|day| day.timeout = None);
+            }
+            "--jobs" | "-j" => {
+                options.jobs = match value().parse::<usize>().unwrap() {
+                    0 => std::thread::available_parallelism().unwrap().get(),
+                    jobs => jobs,
+                }
+            }
+            // Already loaded before any other option is applied
+            "--config" => drop(value()),
+            "--data-dir" => options.data_dir = value().into(),
+            "--year" => options.inputs.server.year = value().parse().unwrap(),
+            "--format" => {
+                options.format = match value().as_str() {
+                    "text" => OutputFormat::Text,
+                    "json" => OutputFormat::Json,
+                    format => panic!("invalid output format {format}"),
+                }
+            }
+            "--color" => options.color = Some(true),
+            "--no-color" => options.color = Some(false),
+            // Options of the gen, fuzz and serve commands, which handle them themselves
+            "--size" | "--seed" | "--iterations" | "--port" => {
+                let value = value();
+                positional.extend([word, value]);
+            }
+            flag if flag.starts_with("--") => panic!("unknown option {flag}"),
+            _ => positional.push(word),
+        }
+    }
+    positional
+}
+
+/// Seed for random inputs, printed since the inputs could not be generated again without it
+fn time_seed() -> u64 {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    eprintln!("seed: {seed}");
+    seed
+}
+
+/// Path of the config file, given by `--config <path>` or the default
+fn config_path(words: &[String]) -> PathBuf {
+    words
+        .iter()
+        .position(|word| word == "--config")
+        .map(|index| PathBuf::from(words.get(index + 1).expect("missing value for --config")))
+        .unwrap_or_else(|| PathBuf::from(config::DEFAULT_PATH))
 }
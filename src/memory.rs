@@ -0,0 +1,182 @@
+//! Heap usage tracking. Building with the `memory-stats` feature installs a counting global allocator;
+//! without it, no stats are collected and [`MemoryTracker::finish`] always returns `None`.
+//!
+//! Counters are kept per thread, so days running concurrently don't affect each other's stats.
+//! Allocations made by threads that a solution spawns itself are not counted.
+
+use std::fmt::{Display, Formatter};
+
+/// Heap usage of a single part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Highest number of heap bytes in use at once, on top of what was in use when the part started
+    pub peak_bytes: u64,
+    /// Number of allocations, including reallocations
+    pub allocations: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations",
+            Bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Formats a byte count using binary units, e.g. `1.5 MiB`
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{value:.1} {}", UNITS[unit])
+        }
+    }
+}
+
+/// Measures heap usage on the current thread from its creation until [`finish`](Self::finish)
+pub(crate) struct MemoryTracker {
+    #[cfg_attr(not(feature = "memory-stats"), allow(dead_code))]
+    start: counting::Snapshot,
+}
+
+impl MemoryTracker {
+    pub(crate) fn start() -> Self {
+        Self {
+            start: counting::start(),
+        }
+    }
+
+    pub(crate) fn finish(&self) -> Option<MemoryStats> {
+        #[cfg(feature = "memory-stats")]
+        return Some(counting::finish(&self.start));
+        #[cfg(not(feature = "memory-stats"))]
+        None
+    }
+}
+
+#[cfg(feature = "memory-stats")]
+mod counting {
+    use super::MemoryStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    thread_local! {
+        // Bytes currently allocated by this thread; may go negative if memory allocated elsewhere is freed here
+        static CURRENT: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+        static COUNT: Cell<u64> = const { Cell::new(0) };
+    }
+
+    struct CountingAllocator;
+
+    fn record(diff: i64, is_allocation: bool) {
+        // Thread-locals may already be destroyed while a thread exits
+        let _ = CURRENT.try_with(|current| {
+            let value = current.get() + diff;
+            current.set(value);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(value)));
+        });
+        if is_allocation {
+            let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record(layout.size() as i64, true);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record(-(layout.size() as i64), false);
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record(layout.size() as i64, true);
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record(new_size as i64 - layout.size() as i64, true);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    pub(super) struct Snapshot {
+        current: i64,
+        count: u64,
+    }
+
+    pub(super) fn start() -> Snapshot {
+        let current = CURRENT.with(Cell::get);
+        // Only peaks reached from now on are relevant
+        PEAK.with(|peak| peak.set(current));
+        Snapshot {
+            current,
+            count: COUNT.with(Cell::get),
+        }
+    }
+
+    pub(super) fn finish(start: &Snapshot) -> MemoryStats {
+        MemoryStats {
+            peak_bytes: (PEAK.with(Cell::get) - start.current).max(0) as u64,
+            allocations: COUNT.with(Cell::get) - start.count,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::hint::black_box;
+
+        #[test]
+        fn counts_allocations() {
+            let snapshot = start();
+            let big = black_box(vec![0u8; 10_000]);
+            drop(big);
+            let small = black_box(Box::new(5u64));
+            let stats = finish(&snapshot);
+            assert_eq!(stats.allocations, 2);
+            assert!(stats.peak_bytes >= 10_000, "peak was {}", stats.peak_bytes);
+            drop(small);
+        }
+    }
+}
+
+#[cfg(not(feature = "memory-stats"))]
+mod counting {
+    pub(super) struct Snapshot;
+
+    pub(super) fn start() -> Snapshot {
+        Snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0 MiB");
+    }
+}
@@ -0,0 +1,109 @@
+//! Minimal worker pool used to run days concurrently
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+/// Runs `run` for every task on a pool of `jobs` threads.
+/// `on_done` is called on the current thread with each result, in the order of the tasks rather than the order they finished.
+pub(crate) fn run_ordered<T, R>(
+    jobs: usize,
+    tasks: &[T],
+    run: impl Fn(&T) -> R + Sync,
+    mut on_done: impl FnMut(&T, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let sender = sender.clone();
+            let (next_task, run) = (&next_task, &run);
+            scope.spawn(move || {
+                loop {
+                    let index = next_task.fetch_add(1, Ordering::Relaxed);
+                    if index >= tasks.len() {
+                        break;
+                    }
+                    sender.send((index, run(&tasks[index]))).unwrap();
+                }
+            });
+        }
+        drop(sender);
+
+        // Results that finished before some earlier task
+        let mut pending = BTreeMap::new();
+        let mut next_to_report = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_to_report) {
+                on_done(&tasks[next_to_report], result);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+/// CPU time the current thread has used so far; only known on Linux, from `/proc`
+pub(crate) fn thread_cpu_time() -> Option<Duration> {
+    let stats = fs::read_to_string("/proc/thread-self/schedstat").ok()?;
+    let nanos = stats.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_nanos(nanos))
+}
+
+/// Runs `f`, converting a panic into its message
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn reports_in_task_order() {
+        let tasks = (0..20u64).collect::<Vec<_>>();
+        let mut reported = Vec::new();
+        run_ordered(
+            4,
+            &tasks,
+            |&task| {
+                // Make earlier tasks finish later
+                thread::sleep(Duration::from_millis(20 - task));
+                task * 2
+            },
+            |&task, result| reported.push((task, result)),
+        );
+        assert_eq!(
+            reported,
+            tasks.iter().map(|&t| (t, t * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 5), Ok(5));
+        assert_eq!(
+            catch_panic(|| -> u8 { panic!("oh no {}", 1) }),
+            Err(String::from("oh no 1"))
+        );
+    }
+}
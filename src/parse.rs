@@ -0,0 +1,66 @@
+//! Helpers for the parsing stage of each day ([`DayInfo::parse`](crate::DayInfo::parse)), which rejects malformed
+//! input with a [`ParseError`] instead of panicking; the `fuzz` command checks that it does.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {message}")]
+pub struct ParseError {
+    /// 1-based line number where the problem was found
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Display) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Lines of the input along with their 1-based line numbers
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> + Clone {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// Parses a number found on the given line
+pub fn number<T: FromStr>(line: usize, text: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|err| ParseError::new(line, format!("invalid number {text:?}: {err}")))
+}
+
+/// Parses a list of numbers separated by `separator`
+pub fn numbers<T: FromStr>(line: usize, text: &str, separator: char) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    text.split(separator)
+        .map(|number| self::number(line, number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_lines() {
+        assert_eq!(numbers::<u8>(3, "1,2,3", ','), Ok(vec![1, 2, 3]));
+        let err = number::<u8>(7, "300").unwrap_err();
+        assert_eq!(err.line, 7);
+        assert_eq!(
+            err.to_string(),
+            "line 7: invalid number \"300\": number too large to fit in target type"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Live progress line for long-running parts, see [`Context::progress`](crate::Context::progress).
+//!
+//! The line is drawn on stderr, and only when it is a terminal that logs aren't written to, so piped or logged output
+//! never contains it. Days running concurrently share the line, and it is erased as soon as a day finishes.
+
+use std::collections::BTreeMap;
+use std::io::{Write, stderr};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two redraws, so reporting progress in a loop stays cheap
+const INTERVAL: Duration = Duration::from_millis(100);
+/// Longest line drawn, longer ones would wrap and couldn't be erased anymore
+const MAX_WIDTH: usize = 79;
+
+static LINE: Mutex<ProgressLine> = Mutex::new(ProgressLine {
+    days: BTreeMap::new(),
+    last_draw: None,
+    shown: false,
+});
+
+/// Progress of every day currently reporting it
+struct ProgressLine {
+    /// Part, items done and total items, keyed by day number
+    days: BTreeMap<u8, (usize, usize, usize)>,
+    last_draw: Option<Instant>,
+    shown: bool,
+}
+
+impl ProgressLine {
+    fn draw(&mut self) {
+        let mut line = self
+            .days
+            .iter()
+            .map(|(day, &(part, done, total))| {
+                let percent = 100 * done / total.max(1);
+                format!("day {day} part {part}: {done}/{total} ({percent}%)")
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        if let Some((index, _)) = line.char_indices().nth(MAX_WIDTH) {
+            line.truncate(index);
+        }
+        let mut stderr = stderr().lock();
+        write!(stderr, "\r{line}\x1b[K").unwrap();
+        stderr.flush().unwrap();
+        self.last_draw = Some(Instant::now());
+        self.shown = true;
+    }
+
+    fn clear(&mut self) {
+        if self.shown {
+            let mut stderr = stderr().lock();
+            write!(stderr, "\r\x1b[K").unwrap();
+            stderr.flush().unwrap();
+            self.shown = false;
+            // Draw again at the next update
+            self.last_draw = None;
+        }
+    }
+}
+
+/// Erases the progress line, e.g. before printing something else to the terminal; it is drawn again at the next update
+pub(crate) fn clear() {
+    LINE.lock().unwrap_or_else(PoisonError::into_inner).clear();
+}
+
+/// Per-day progress reporter, owned by the [`Context`](crate::Context); removes the day from the line when dropped
+pub(crate) struct Progress {
+    day: u8,
+    enabled: bool,
+}
+
+impl Progress {
+    pub(crate) fn new(day: u8, enabled: bool) -> Self {
+        Self { day, enabled }
+    }
+
+    pub(crate) fn update(&self, part: usize, done: usize, total: usize) {
+        if !self.enabled {
+            return;
+        }
+        let mut line = LINE.lock().unwrap_or_else(PoisonError::into_inner);
+        line.days.insert(self.day, (part, done, total));
+        if line.last_draw.is_none_or(|time| time.elapsed() >= INTERVAL) {
+            line.draw();
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let mut line = LINE.lock().unwrap_or_else(PoisonError::into_inner);
+        if line.days.remove(&self.day).is_some() {
+            if line.days.is_empty() {
+                line.clear();
+            } else {
+                line.draw();
+            }
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! Reports the outcome of each day as it finishes: an aligned table with one row per part in the text format,
+//! or one JSON object per day. Answers are compared with the expected ones where they are known, i.e. for full
+//! runs once the answer was accepted by the puzzle server (see [`submit`](crate::submit)), and for example files
+//! that come with expected answers (see [`examples`](crate::examples)).
+
+use crate::Part::{Both, One};
+use crate::RunType::{Examples, Full};
+use crate::answer::Answer;
+use crate::progress;
+use crate::submit::{self, Verdict};
+use crate::{DayJob, DayOutcome, OutputFormat, RunOptions};
+use serde_json::json;
+use std::fmt::{Display, Formatter};
+use std::io::{Write, stdout};
+use std::time::Duration;
+
+/// Whether an answer matches the expected one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Check {
+    Pass,
+    Fail,
+    /// No expected answer is known
+    Unknown,
+    /// The part has no answer, and none was expected
+    NotApplicable,
+}
+
+impl Check {
+    pub(crate) fn of(answer: &Answer, expected: Option<&Answer>) -> Self {
+        match expected {
+            None if answer.matches(&Answer::NotApplicable) => Check::NotApplicable,
+            Some(expected) if expected.matches(answer) => Check::Pass,
+            Some(_) => Check::Fail,
+            None => Check::Unknown,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail => "fail",
+            Check::Unknown => "unknown",
+            Check::NotApplicable => "n/a",
+        }
+    }
+}
+
+/// Formats a duration with a unit that fits its size, e.g. `12.3 µs` or `1m 05s`
+pub struct HumanDuration(pub Duration);
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let nanos = self.0.as_nanos();
+        let text = if nanos < 1_000 {
+            format!("{nanos} ns")
+        } else if nanos < 1_000_000 {
+            format!("{:.1} µs", nanos as f64 / 1e3)
+        } else if nanos < 1_000_000_000 {
+            format!("{:.1} ms", nanos as f64 / 1e6)
+        } else if nanos < 60_000_000_000 {
+            format!("{:.2} s", nanos as f64 / 1e9)
+        } else {
+            let seconds = self.0.as_secs();
+            format!("{}m {:02}s", seconds / 60, seconds % 60)
+        };
+        // Support width & alignment, used for table columns
+        f.pad(&text)
+    }
+}
+
+const ANSWER_WIDTH: usize = 20;
+
+#[derive(Debug, Default)]
+struct Totals {
+    /// Number of days run, each example counting separately
+    runs: usize,
+    pass: usize,
+    fail: usize,
+    unknown: usize,
+    errors: usize,
+    timeouts: usize,
+    /// Sum of the runtimes of all days that finished
+    elapsed: Duration,
+    /// Sum of the CPU times of all days that finished
+    cpu_time: Duration,
+    /// Whether the CPU time of some day couldn't be measured
+    cpu_time_missing: bool,
+}
+
+pub(crate) struct Report {
+    format: OutputFormat,
+    color: bool,
+    totals: Totals,
+}
+
+impl Report {
+    pub(crate) fn new(options: &RunOptions) -> Self {
+        Self {
+            format: options.format,
+            color: options.color_enabled(),
+            totals: Totals::default(),
+        }
+    }
+
+    pub(crate) fn header(&self) {
+        if self.format == OutputFormat::Text {
+            println!();
+            println!(
+                "{}",
+                row(["day", "name", "part", "status", "answer", "time"])
+            );
+        }
+    }
+
+    /// Reports a finished day
+    pub(crate) fn day(&mut self, job: &DayJob, outcome: &DayOutcome) {
+        let expected = expected_answers(job);
+        // Days still running (or timed out) may be drawing their progress on the same terminal
+        progress::clear();
+        match self.format {
+            OutputFormat::Text => {
+                let mut stdout = stdout().lock();
+                for line in self.day_lines(job, outcome, &expected) {
+                    writeln!(stdout, "{line}").unwrap();
+                }
+            }
+            OutputFormat::Json => println!("{}", json_report(job, outcome, &expected)),
+        }
+    }
+
+    fn day_lines(
+        &mut self,
+        job: &DayJob,
+        outcome: &DayOutcome,
+        expected: &[Option<Answer>],
+    ) -> Vec<String> {
+        self.totals.runs += 1;
+        let day = job.day_num.to_string();
+        let name = job
+            .example
+            .as_ref()
+            .map_or(job.day.name, |example| &example.name);
+        let run = match outcome {
+            DayOutcome::Done(run) => run,
+            DayOutcome::Failed(message) => {
+                self.totals.errors += 1;
+                let status = self.paint("error", Paint::Red);
+                return vec![format!(
+                    "{}  {message}",
+                    row([&day, name, "-", &status, "", ""])
+                )];
+            }
+            DayOutcome::TimedOut => {
+                self.totals.timeouts += 1;
+                let status = self.paint("timeout", Paint::Red);
+                let timeout = job.options.timeout_for(job.day_num).unwrap_or_default();
+                let timeout = HumanDuration(timeout).to_string();
+                return vec![row([&day, name, "-", &status, "", &timeout])];
+            }
+        };
+        self.totals.elapsed += run.elapsed;
+        match run.cpu_time {
+            Some(cpu_time) => self.totals.cpu_time += cpu_time,
+            None => self.totals.cpu_time_missing = true,
+        }
+        let mut lines = Vec::new();
+        for (index, answer) in run.answers.iter().enumerate() {
+            let expected = expected.get(index).and_then(Option::as_ref);
+            let check = Check::of(answer, expected);
+            match check {
+                Check::Pass => self.totals.pass += 1,
+                Check::Fail => self.totals.fail += 1,
+                Check::Unknown => self.totals.unknown += 1,
+                Check::NotApplicable => {}
+            }
+            let status = self.paint(
+                check.label(),
+                match check {
+                    Check::Pass => Paint::Green,
+                    Check::Fail => Paint::Red,
+                    Check::Unknown => Paint::Yellow,
+                    Check::NotApplicable => Paint::Dim,
+                },
+            );
+            let time = run
+                .part_times
+                .get(index)
+                .map(|time| HumanDuration(*time).to_string())
+                .unwrap_or_default();
+            let (day, name) = if index == 0 { (&*day, name) } else { ("", "") };
+            let mut line = row([
+                day,
+                name,
+                &(index + 1).to_string(),
+                &status,
+                &shorten(&answer.to_string()),
+                &time,
+            ]);
+            if let Some(stats) = run.memory.get(index) {
+                line += &format!("  {stats}");
+            }
+            if let Some(counts) = run.counts.get(index).filter(|counts| !counts.is_empty()) {
+                line += &format!("  {counts}");
+            }
+            if let (Check::Fail, Some(expected)) = (check, expected) {
+                line += &format!("  expected {}", shorten(&expected.to_string()));
+            }
+            lines.push(line);
+        }
+        // Answers that don't fit in the table, e.g. ASCII art, are shown in full below it
+        for (index, answer) in run.answers.iter().enumerate() {
+            let text = answer.to_string();
+            if shorten(&text) != text {
+                lines.push(format!("  part {} answer:", index + 1));
+                lines.extend(text.lines().map(|line| format!("    {line}")));
+            }
+        }
+        lines
+    }
+
+    /// Reports the totals of all days. The time column sums the time of each day; when several days ran,
+    /// `wall_time` is the elapsed time of the whole run, shown next to the summed CPU time of the days
+    pub(crate) fn total(&self, wall_time: Option<Duration>) {
+        if self.format == OutputFormat::Text {
+            println!("{}", self.total_line(wall_time));
+        }
+    }
+
+    fn total_line(&self, wall_time: Option<Duration>) -> String {
+        let totals = &self.totals;
+        let mut summary = format!(
+            "total: {} run{}, {} passed, {} failed, {} unknown",
+            totals.runs,
+            if totals.runs == 1 { "" } else { "s" },
+            totals.pass,
+            totals.fail,
+            totals.unknown
+        );
+        if totals.errors > 0 {
+            let plural = if totals.errors == 1 { "" } else { "s" };
+            summary += &format!(", {} error{plural}", totals.errors);
+        }
+        if totals.timeouts > 0 {
+            summary += &format!(", {} timed out", totals.timeouts);
+        }
+        let width = row(["", "", "", "", "", ""]).len() - TIME_WIDTH;
+        let mut line = format!(
+            "{summary:<width$}{:>TIME_WIDTH$}",
+            HumanDuration(totals.elapsed)
+        );
+        if let Some(wall_time) = wall_time {
+            line += &format!("  per-day sum, wall time {}", HumanDuration(wall_time));
+            if !totals.cpu_time_missing {
+                line += &format!(", CPU time {}", HumanDuration(totals.cpu_time));
+            }
+        }
+        line
+    }
+
+    fn paint(&self, text: &str, paint: Paint) -> String {
+        // Pad before adding escape codes, which would otherwise count towards the width
+        let text = format!("{text:<STATUS_WIDTH$}");
+        if !self.color {
+            return text;
+        }
+        let code = match paint {
+            Paint::Green => 32,
+            Paint::Red => 31,
+            Paint::Yellow => 33,
+            Paint::Dim => 2,
+        };
+        format!("\x1b[{code}m{text}\x1b[0m")
+    }
+}
+
+enum Paint {
+    Green,
+    Red,
+    Yellow,
+    Dim,
+}
+
+const STATUS_WIDTH: usize = 7;
+const TIME_WIDTH: usize = 10;
+
+/// Lays out the columns of a table row: day, name, part, status, answer, time
+fn row([day, name, part, status, answer, time]: [&str; 6]) -> String {
+    format!(
+        "{day:>3}  {name:<20}  {part:>4}  {status:<STATUS_WIDTH$}  {answer:<ANSWER_WIDTH$}  {time:>TIME_WIDTH$}"
+    )
+}
+
+/// First line of an answer, cut to fit its column
+pub(crate) fn shorten(answer: &str) -> String {
+    let line = answer.lines().next().unwrap_or("");
+    if line.chars().count() <= ANSWER_WIDTH && !answer.contains('\n') {
+        return line.to_string();
+    }
+    let mut short = line.chars().take(ANSWER_WIDTH - 1).collect::<String>();
+    short.push('…');
+    short
+}
+
+/// Expected answer of each part, where known
+pub(crate) fn expected_answers(job: &DayJob) -> Vec<Option<Answer>> {
+    match job.run_type {
+        Examples => job
+            .example
+            .as_ref()
+            .map_or_else(Vec::new, |example| example.expected.clone()),
+        Full => {
+            // A broken log shouldn't stop the report, the answers are just unknown then
+            let log = submit::load(&job.options.day_dir(job.day_num)).unwrap_or_default();
+            (1..=2)
+                .map(|level| {
+                    log.iter()
+                        .find(|entry| entry.level == level && entry.verdict == Verdict::Correct)
+                        .map(|entry| entry.answer.parse().unwrap())
+                })
+                .collect()
+        }
+    }
+}
+
+fn json_report(job: &DayJob, outcome: &DayOutcome, expected: &[Option<Answer>]) -> String {
+    let mut report = json!({
+        "day": job.day_num,
+        "name": job.day.name,
+        "run_type": match job.run_type {
+            Examples => "examples",
+            Full => "full",
+        },
+        "part": match job.part {
+            One => "1",
+            Both => "both",
+        },
+    });
+    if let Some(example) = &job.example {
+        report["example"] = json!(example.name);
+    }
+    let fields = match outcome {
+        DayOutcome::Done(run) => json!({
+            "status": "done",
+            // No answer is null, everything else is text
+            "answers": run.answers.iter().map(|answer| match answer {
+                Answer::NotApplicable => None,
+                answer => Some(answer.to_string()),
+            }).collect::<Vec<_>>(),
+            "checks": run.answers.iter().enumerate().map(|(index, answer)| {
+                Check::of(answer, expected.get(index).and_then(Option::as_ref)).label()
+            }).collect::<Vec<_>>(),
+            "elapsed_ns": run.elapsed.as_nanos() as u64,
+            "cpu_time_ns": run.cpu_time.map(|time| time.as_nanos() as u64),
+            "part_elapsed_ns": run.part_times.iter().map(|time| time.as_nanos() as u64).collect::<Vec<_>>(),
+            "memory": run.memory.iter().map(|stats| json!({
+                "peak_bytes": stats.peak_bytes,
+                "allocations": stats.allocations,
+            })).collect::<Vec<_>>(),
+            "counters": run.counts,
+        }),
+        DayOutcome::Failed(message) => json!({ "status": "failed", "error": message }),
+        DayOutcome::TimedOut => json!({ "status": "timed_out" }),
+    };
+    report
+        .as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    report.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations() {
+        let format = |nanos| HumanDuration(Duration::from_nanos(nanos)).to_string();
+        assert_eq!(format(999), "999 ns");
+        assert_eq!(format(12_345), "12.3 µs");
+        assert_eq!(format(4_560_000), "4.6 ms");
+        assert_eq!(format(1_234_000_000), "1.23 s");
+        assert_eq!(format(65_000_000_000), "1m 05s");
+        assert_eq!(
+            format!("[{:>8}]", HumanDuration(Duration::from_nanos(5))),
+            "[    5 ns]"
+        );
+    }
+
+    #[test]
+    fn shortens_answers() {
+        assert_eq!(shorten("12345"), "12345");
+        assert_eq!(shorten("#..#\n#..#"), "#..#…");
+        assert_eq!(shorten(&"x".repeat(30)).chars().count(), ANSWER_WIDTH);
+    }
+
+    #[test]
+    #[cfg(feature = "day-1")]
+    fn reports_days() {
+        use crate::counters::Counts;
+        use crate::days;
+        use crate::{DayRun, RunType};
+
+        let options = RunOptions {
+            color: Some(false),
+            ..RunOptions::default()
+        };
+        let job = DayJob {
+            day_num: 1,
+            day: days::get(1).unwrap(),
+            part: Both,
+            run_type: RunType::Examples,
+            options: options.clone(),
+            log_sink: None,
+            example: None,
+            variant: None,
+        };
+        let mut counts = Counts::default();
+        counts.add("turns", 4);
+        let run = DayRun {
+            answers: vec![Answer::Integer(3), Answer::Integer(6)],
+            elapsed: Duration::from_micros(30),
+            cpu_time: Some(Duration::from_micros(28)),
+            part_times: vec![Duration::from_micros(20), Duration::from_micros(10)],
+            memory: Vec::new(),
+            counts: vec![Counts::default(), counts],
+        };
+        let mut report = Report::new(&options);
+        let lines = report.day_lines(
+            &job,
+            &DayOutcome::Done(run),
+            &[Some(Answer::Integer(3)), Some(Answer::Integer(7))],
+        );
+        assert_eq!(
+            lines,
+            [
+                "  1  Secret Entrance          1  pass     3                        20.0 µs",
+                "                              2  fail     6                        10.0 µs  turns=4  expected 7",
+            ]
+        );
+        report.day_lines(&job, &DayOutcome::Failed("oops".to_string()), &[]);
+        assert_eq!(
+            report.total_line(None),
+            "total: 2 runs, 1 passed, 1 failed, 0 unknown, 1 error              30.0 µs"
+        );
+        assert!(
+            report
+                .total_line(Some(Duration::from_micros(25)))
+                .ends_with("30.0 µs  per-day sum, wall time 25.0 µs, CPU time 28.0 µs")
+        );
+
+        let run = DayRun {
+            answers: vec![Answer::art(["#..#", "####"]), Answer::NotApplicable],
+            elapsed: Duration::ZERO,
+            cpu_time: None,
+            part_times: Vec::new(),
+            memory: Vec::new(),
+            counts: Vec::new(),
+        };
+        let lines = report.day_lines(&job, &DayOutcome::Done(run), &[]);
+        assert_eq!(lines[2..], ["  part 1 answer:", "    #..#", "    ####"]);
+    }
+}
@@ -0,0 +1,751 @@
+//! Local web dashboard started by the `serve` command: the list of days with their latest answers and whether they
+//! match the accepted ones, and for each day its timing history, its visualization frames, and buttons to run it again.
+//!
+//! Pages are rendered by the server itself, without scripts or external assets, so the dashboard works offline.
+//! The server only listens on the loopback interface, and only answers requests made to that address from its own
+//! pages, so other websites can't start runs or read answers through the browser.
+
+use crate::Part::Both;
+use crate::RunType::{Examples, Full};
+use crate::answer::Answer;
+use crate::history::{self, HistoryEntry, Timestamp};
+use crate::report::{self, Check, HumanDuration};
+use crate::viz::Svg;
+use crate::{DayJob, DayOutcome, RunOptions, RunType, days, parallel};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+/// Number of most recent runs shown in a day's timing history
+const HISTORY_LENGTH: usize = 30;
+/// Maximum number of frames shown per run
+const MAX_FRAMES: usize = 50;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.3em 0.8em; text-align: left; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+.pass { color: #1a7f37; } .fail { color: #cf222e; } .unknown { color: #9a6700; } .na { color: #888; }
+pre { background: #f6f8fa; padding: 0.5em; overflow-x: auto; line-height: 1.1; }
+.chart svg { width: 100%; height: 160px; }
+.frame img { max-width: 800px; image-rendering: pixelated; border: 1px solid #ddd; }
+form { display: inline; }
+";
+
+/// Serves the dashboard on `127.0.0.1:<port>` until the process is stopped
+pub fn serve(port: u16, options: &RunOptions) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Dashboard running on http://{}/", listener.local_addr()?);
+    let dashboard = Dashboard {
+        port: listener.local_addr()?.port(),
+        // Runs started from the dashboard save their frames, so they can be shown
+        options: RunOptions {
+            visualize: true,
+            ..options.clone()
+        },
+        last_runs: Mutex::default(),
+        running: Mutex::new(()),
+    };
+    let dashboard = &dashboard;
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    scope.spawn(move || {
+                        if let Err(err) = dashboard.handle(stream) {
+                            eprintln!("dashboard request failed: {err}");
+                        }
+                    });
+                }
+                Err(err) => eprintln!("dashboard connection failed: {err}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+struct Dashboard {
+    /// Port the dashboard listens on, which requests must be addressed to
+    port: u16,
+    options: RunOptions,
+    /// Outcome of the latest run started from the dashboard, rendered, by day
+    last_runs: Mutex<HashMap<u8, String>>,
+    /// Held while a day runs, since runs of the same day would write the same files
+    running: Mutex<()>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    /// `Host` header, the address the request was sent to
+    host: Option<String>,
+    /// `Origin` header, the site the request was made from, when sent by a browser
+    origin: Option<String>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    location: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn html(title: &str, body: &str) -> Self {
+        let page = format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{STYLE}</style></head>\n<body>\n{body}</body></html>\n",
+            escape(title)
+        );
+        Self::file("text/html; charset=utf-8", page.into_bytes())
+    }
+
+    fn file(content_type: &'static str, body: Vec<u8>) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            location: None,
+            body,
+        }
+    }
+
+    fn redirect(location: String) -> Self {
+        Self {
+            status: "303 See Other",
+            content_type: "text/plain; charset=utf-8",
+            location: Some(location),
+            body: Vec::new(),
+        }
+    }
+
+    fn forbidden() -> Self {
+        Self {
+            status: "403 Forbidden",
+            content_type: "text/plain; charset=utf-8",
+            location: None,
+            body: b"forbidden\n".to_vec(),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            content_type: "text/plain; charset=utf-8",
+            location: None,
+            body: b"not found\n".to_vec(),
+        }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )?;
+        if let Some(location) = &self.location {
+            write!(stream, "Location: {location}\r\n")?;
+        }
+        stream.write_all(b"\r\n")?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+/// Reads the request line and headers, and skips the body
+fn read_request(reader: &mut impl BufRead) -> io::Result<Option<Request>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        host: None,
+        origin: None,
+    };
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().unwrap_or(0);
+        } else if name.eq_ignore_ascii_case("host") {
+            request.host = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            request.origin = Some(value.to_string());
+        }
+    }
+    io::copy(&mut reader.take(content_length), &mut io::sink())?;
+    Ok(Some(request))
+}
+
+impl Dashboard {
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        let Some(request) = read_request(&mut BufReader::new(&stream))? else {
+            return Ok(());
+        };
+        let response = self.route(&request);
+        response.write_to(&mut stream)
+    }
+
+    /// Whether the request was sent to the dashboard from its own pages, or from outside a browser. This rejects
+    /// forms posted by other websites (different `Origin`) and pages read through DNS rebinding (different `Host`)
+    fn is_local(&self, request: &Request) -> bool {
+        let local = |host: &str| {
+            ["127.0.0.1", "localhost"]
+                .iter()
+                .any(|name| host.eq_ignore_ascii_case(&format!("{name}:{}", self.port)))
+        };
+        request.host.as_deref().is_some_and(local)
+            && request
+                .origin
+                .as_deref()
+                .is_none_or(|origin| origin.strip_prefix("http://").is_some_and(local))
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        if !self.is_local(request) {
+            return Response::forbidden();
+        }
+        let segments = request
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        let day = |text: &str| {
+            text.parse::<u8>()
+                .ok()
+                .filter(|&day| days::get(day).is_some())
+        };
+        match (request.method.as_str(), &segments[..]) {
+            ("GET", []) => self.index(),
+            ("GET", ["day", day_num]) => {
+                day(day_num).map_or_else(Response::not_found, |day| self.day(day))
+            }
+            ("POST", ["day", day_num, "run"]) => match day(day_num) {
+                Some(day) => {
+                    let run_type = if request.query == "mode=e" {
+                        Examples
+                    } else {
+                        Full
+                    };
+                    self.run(day, run_type);
+                    Response::redirect(format!("/day/{day}"))
+                }
+                None => Response::not_found(),
+            },
+            ("GET", ["day", day_num, "viz", frame @ ..]) => day(day_num)
+                .and_then(|day| self.frame(day, frame))
+                .unwrap_or_else(Response::not_found),
+            _ => Response::not_found(),
+        }
+    }
+
+    /// Expected answers of a full run, where known
+    fn expected(&self, day_num: u8) -> Vec<Option<Answer>> {
+        let job = crate::day_jobs(day_num, Both, Full, &self.options, None);
+        report::expected_answers(&job[0])
+    }
+
+    fn index(&self) -> Response {
+        let mut body = String::from(
+            "<h1>Advent of Code 2025</h1>\n<table>\n<tr><th>day</th><th>name</th><th>part 1</th><th></th><th>part 2</th><th></th><th>time</th><th>full runs</th><th>last run</th></tr>\n",
+        );
+        for (day_num, day) in days::enabled() {
+            let history = history::load(&self.options.day_dir(day_num)).unwrap_or_default();
+            write!(body, "<tr><td><a href=\"/day/{day_num}\">{day_num}</a></td><td><a href=\"/day/{day_num}\">{}</a></td>", escape(day.name)).unwrap();
+            match history.last() {
+                Some(entry) => {
+                    let expected = self.expected(day_num);
+                    for index in 0..2 {
+                        body += &answer_cells(
+                            entry.answers.get(index),
+                            expected.get(index).and_then(Option::as_ref),
+                        );
+                    }
+                    write!(
+                        body,
+                        "<td class=\"number\">{}</td><td class=\"number\">{}</td><td>{}</td>",
+                        HumanDuration(entry.elapsed),
+                        history.len(),
+                        Timestamp(entry.timestamp)
+                    )
+                    .unwrap();
+                }
+                None => body += "<td colspan=\"7\" class=\"na\">no full runs yet</td>",
+            }
+            body += "</tr>\n";
+        }
+        body += "</table>\n";
+        Response::html("Advent of Code 2025", &body)
+    }
+
+    fn day(&self, day_num: u8) -> Response {
+        let day = days::get(day_num).unwrap();
+        let day_dir = self.options.day_dir(day_num);
+        let mut body = format!(
+            "<p><a href=\"/\">all days</a></p>\n<h1>Day {day_num}: {}</h1>\n<p>Run again: \
+             <form method=\"post\" action=\"/day/{day_num}/run?mode=f\"><button>full input</button></form> \
+             <form method=\"post\" action=\"/day/{day_num}/run?mode=e\"><button>examples</button></form></p>\n",
+            escape(day.name)
+        );
+        let last_run = self
+            .last_runs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&day_num)
+            .cloned();
+        if let Some(last_run) = last_run {
+            body += "<h2>Last run from the dashboard</h2>\n";
+            body += &last_run;
+        }
+
+        body += "<h2>Timing history</h2>\n";
+        match history::load(&day_dir) {
+            Ok(history) if history.is_empty() => body += "<p class=\"na\">No full runs yet</p>\n",
+            Ok(history) => body += &history_html(&history, &self.expected(day_num)),
+            Err(err) => writeln!(
+                body,
+                "<p class=\"fail\">Could not load the history: {}</p>",
+                escape(&err.to_string())
+            )
+            .unwrap(),
+        }
+
+        body += "<h2>Visualization frames</h2>\n";
+        let runs = frame_runs(&day_dir.join("viz"));
+        if runs.is_empty() {
+            body += "<p class=\"na\">No frames yet; they are saved when the day is run from here, or with --viz</p>\n";
+        }
+        for (run, frames) in runs {
+            writeln!(body, "<h3>{}</h3>", escape(&run)).unwrap();
+            if frames.len() > MAX_FRAMES {
+                writeln!(
+                    body,
+                    "<p class=\"na\">Showing the first {MAX_FRAMES} of {} frames</p>",
+                    frames.len()
+                )
+                .unwrap();
+            }
+            for frame in frames.iter().take(MAX_FRAMES) {
+                body += &frame_html(day_num, &day_dir.join("viz"), &run, frame);
+            }
+        }
+        Response::html(&format!("Day {day_num}: {}", day.name), &body)
+    }
+
+    /// Runs a day like the `run` command would, keeping the rendered outcome for the day's page
+    fn run(&self, day_num: u8, run_type: RunType) {
+        let _running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        let rendered = parallel::catch_panic(|| {
+            let jobs = crate::day_jobs(
+                day_num,
+                Both,
+                run_type,
+                &self.options,
+                crate::open_log_sink(&self.options),
+            );
+            let mut html = String::from(
+                "<table>\n<tr><th>input</th><th>part</th><th>status</th><th>answer</th><th>time</th><th>counters</th></tr>\n",
+            );
+            for job in &jobs {
+                let outcome = crate::run_day_guarded(job);
+                html += &outcome_html(job, &outcome, &report::expected_answers(job));
+            }
+            html + "</table>\n"
+        });
+        let rendered = rendered
+            .unwrap_or_else(|message| format!("<p class=\"fail\">{}</p>\n", escape(&message)));
+        self.last_runs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(day_num, rendered);
+    }
+
+    /// A saved frame, as a file browsers can show
+    fn frame(&self, day_num: u8, path: &[&str]) -> Option<Response> {
+        // Only plain names, so requests can't leave the frames directory
+        let plain = |segment: &&str| {
+            !segment.starts_with('.')
+                && segment
+                    .chars()
+                    .all(|char| char.is_ascii_alphanumeric() || "-_.".contains(char))
+        };
+        if !path.iter().all(plain) {
+            return None;
+        }
+        let file = path
+            .iter()
+            .fold(self.options.day_dir(day_num).join("viz"), |dir, segment| {
+                dir.join(segment)
+            });
+        let contents = fs::read(&file).ok()?;
+        Some(match file.extension()?.to_str()? {
+            "txt" => Response::file("text/plain; charset=utf-8", contents),
+            "svg" => Response::file("image/svg+xml", contents),
+            "ppm" => Response::file("image/bmp", ppm_to_bmp(&contents)?),
+            _ => return None,
+        })
+    }
+}
+
+/// Answer and status cells of one part
+fn answer_cells(answer: Option<&Answer>, expected: Option<&Answer>) -> String {
+    let Some(answer) = answer else {
+        return "<td></td><td></td>".to_string();
+    };
+    let check = Check::of(answer, expected);
+    let mut cells = format!(
+        "<td>{}</td><td class=\"{}\">{}",
+        escape(&report::shorten(&answer.to_string())),
+        check_class(check),
+        check.label()
+    );
+    if let (Check::Fail, Some(expected)) = (check, expected) {
+        write!(
+            cells,
+            ", expected {}",
+            escape(&report::shorten(&expected.to_string()))
+        )
+        .unwrap();
+    }
+    cells + "</td>"
+}
+
+fn check_class(check: Check) -> &'static str {
+    match check {
+        Check::Pass => "pass",
+        Check::Fail => "fail",
+        Check::Unknown => "unknown",
+        Check::NotApplicable => "na",
+    }
+}
+
+/// Rows of a finished run, one per part
+fn outcome_html(job: &DayJob, outcome: &DayOutcome, expected: &[Option<Answer>]) -> String {
+    let input = match (&job.example, job.run_type) {
+        (Some(example), _) => escape(&example.name),
+        (None, Examples) => "built-in example".to_string(),
+        (None, Full) => "full input".to_string(),
+    };
+    let run = match outcome {
+        DayOutcome::Done(run) => run,
+        DayOutcome::Failed(message) => {
+            return format!(
+                "<tr><td>{input}</td><td></td><td class=\"fail\">error</td><td colspan=\"3\"><pre>{}</pre></td></tr>\n",
+                escape(message)
+            );
+        }
+        DayOutcome::TimedOut => {
+            return format!(
+                "<tr><td>{input}</td><td></td><td class=\"fail\">timeout</td><td colspan=\"3\"></td></tr>\n"
+            );
+        }
+    };
+    let mut rows = String::new();
+    for (index, answer) in run.answers.iter().enumerate() {
+        let time = run
+            .part_times
+            .get(index)
+            .map(|time| HumanDuration(*time).to_string())
+            .unwrap_or_default();
+        let counts = run
+            .counts
+            .get(index)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        writeln!(
+            rows,
+            "<tr><td>{}</td><td>{}</td>{}<td class=\"number\">{time}</td><td>{}</td></tr>",
+            if index == 0 { &input } else { "" },
+            index + 1,
+            status_then_answer(answer, expected.get(index).and_then(Option::as_ref)),
+            escape(&counts)
+        )
+        .unwrap();
+    }
+    rows
+}
+
+/// Status and answer cells, in that order
+fn status_then_answer(answer: &Answer, expected: Option<&Answer>) -> String {
+    let check = Check::of(answer, expected);
+    format!(
+        "<td class=\"{}\">{}</td><td><pre>{}</pre></td>",
+        check_class(check),
+        check.label(),
+        escape(&answer.to_string())
+    )
+}
+
+/// Chart and table of the most recent full runs
+fn history_html(history: &[HistoryEntry], expected: &[Option<Answer>]) -> String {
+    let recent = &history[history.len().saturating_sub(HISTORY_LENGTH)..];
+    let slowest = recent
+        .iter()
+        .map(|entry| entry.elapsed.as_secs_f64())
+        .fold(0.0, f64::max);
+    // One bar per run, as high as its runtime compared to the slowest one
+    let mut chart = Svg::new(0.0, 0.0, 10.0 * recent.len() as f64, 100.0);
+    for (index, entry) in recent.iter().enumerate() {
+        let height = 100.0 * entry.elapsed.as_secs_f64() / slowest.max(f64::MIN_POSITIVE);
+        chart.rect(
+            (10.0 * index as f64 + 1.0, 100.0 - height),
+            (8.0, height),
+            "#4c78a8",
+            "none",
+        );
+    }
+    let mut html = format!("<div class=\"chart\">{}</div>\n", chart.render());
+    html += "<table>\n<tr><th>time</th><th>part</th><th>source</th><th>runtime</th><th>part 1</th><th></th><th>part 2</th><th></th><th>changes</th></tr>\n";
+    let changes = history::changes(history);
+    for (entry, changes) in changes.iter().rev().take(HISTORY_LENGTH) {
+        let mut notes = Vec::new();
+        if changes.first_run {
+            notes.push("first run");
+        }
+        if changes.answers {
+            notes.push("answers changed");
+        }
+        if changes.source {
+            notes.push("source changed");
+        }
+        if changes.runtime {
+            notes.push("runtime changed");
+        }
+        write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td><code>{:016x}</code></td><td class=\"number\">{}</td>",
+            Timestamp(entry.timestamp),
+            if entry.part == Both { "both" } else { "1" },
+            entry.source_hash,
+            HumanDuration(entry.elapsed)
+        )
+        .unwrap();
+        for index in 0..2 {
+            html += &answer_cells(
+                entry.answers.get(index),
+                expected.get(index).and_then(Option::as_ref),
+            );
+        }
+        writeln!(html, "<td>{}</td></tr>", notes.join(", ")).unwrap();
+    }
+    html + "</table>\n"
+}
+
+/// Frame files of each run saved under `viz_dir`, e.g. `full` or `examples/large.txt`, sorted by name
+fn frame_runs(viz_dir: &Path) -> Vec<(String, Vec<String>)> {
+    fn visit(dir: &Path, run: &str, runs: &mut Vec<(String, Vec<String>)>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut entries = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<PathBuf>>();
+        entries.sort();
+        let mut frames = Vec::new();
+        for path in entries {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            if path.is_dir() {
+                let run = if run.is_empty() {
+                    name
+                } else {
+                    format!("{run}/{name}")
+                };
+                visit(&path, &run, runs);
+            } else {
+                frames.push(name);
+            }
+        }
+        if !frames.is_empty() {
+            runs.push((run.to_string(), frames));
+        }
+    }
+
+    let mut runs = Vec::new();
+    visit(viz_dir, "", &mut runs);
+    runs.sort();
+    runs
+}
+
+fn frame_html(day_num: u8, viz_dir: &Path, run: &str, frame: &str) -> String {
+    let url = format!("/day/{day_num}/viz/{run}/{frame}");
+    let content = if frame.ends_with(".txt") {
+        let text = fs::read_to_string(viz_dir.join(run).join(frame)).unwrap_or_default();
+        format!("<pre>{}</pre>", escape(&text))
+    } else {
+        format!("<img src=\"{}\" alt=\"{}\">", escape(&url), escape(frame))
+    };
+    format!(
+        "<div class=\"frame\"><p><a href=\"{}\">{}</a></p>{content}</div>\n",
+        escape(&url),
+        escape(frame)
+    )
+}
+
+/// Escapes text for HTML content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// Converts a binary PPM image, as saved by [`Image`](crate::viz::Image), to a 24-bit BMP, which browsers can show
+fn ppm_to_bmp(ppm: &[u8]) -> Option<Vec<u8>> {
+    // Header: magic number, width, height and maximum value, each followed by a single whitespace
+    let mut fields = Vec::new();
+    let mut start = 0;
+    for (index, byte) in ppm.iter().enumerate() {
+        if fields.len() == 4 {
+            break;
+        }
+        if byte.is_ascii_whitespace() {
+            fields.push(std::str::from_utf8(&ppm[start..index]).ok()?);
+            start = index + 1;
+        }
+    }
+    let ["P6", width, height, "255"] = fields[..] else {
+        return None;
+    };
+    let (width, height) = (width.parse::<usize>().ok()?, height.parse::<usize>().ok()?);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    // A malformed header may give sizes that don't even fit in memory
+    let length = width.checked_mul(height)?.checked_mul(3)?;
+    let pixels = ppm.get(start..start.checked_add(length)?)?;
+
+    // Rows are stored bottom to top, each padded to a multiple of 4 bytes, with pixels in BGR order
+    let row_size = (3 * width).div_ceil(4) * 4;
+    let size = 54 + row_size * height;
+    let mut bmp = Vec::with_capacity(size);
+    bmp.extend(b"BM");
+    bmp.extend(u32::try_from(size).ok()?.to_le_bytes());
+    bmp.extend([0; 4]);
+    bmp.extend(54u32.to_le_bytes());
+    bmp.extend(40u32.to_le_bytes());
+    bmp.extend(i32::try_from(width).ok()?.to_le_bytes());
+    bmp.extend(i32::try_from(height).ok()?.to_le_bytes());
+    bmp.extend(1u16.to_le_bytes());
+    bmp.extend(24u16.to_le_bytes());
+    bmp.extend([0; 24]);
+    for row in pixels.chunks(3 * width).rev() {
+        for pixel in row.chunks(3) {
+            bmp.extend([pixel[2], pixel[1], pixel[0]]);
+        }
+        bmp.extend(vec![0; row_size - 3 * width]);
+    }
+    Some(bmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::viz::Image;
+
+    #[test]
+    fn converts_ppm_to_bmp() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set(1, 0, [255, 0, 10]);
+        let bmp = ppm_to_bmp(&image.to_ppm()).unwrap();
+        assert_eq!(bmp.len(), 54 + 8);
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(&bmp[54..], [0, 0, 0, 10, 0, 255, 0, 0]);
+        assert_eq!(ppm_to_bmp(b"P3\n1 1\n255\n"), None);
+        assert_eq!(ppm_to_bmp(b"P6\n0 1\n255\n"), None);
+        assert_eq!(ppm_to_bmp(b"P6\n1 0\n255\n"), None);
+        let huge = format!("P6\n{} 2\n255\n", usize::MAX / 2);
+        assert_eq!(ppm_to_bmp(huge.as_bytes()), None);
+        let huge = format!("P6\n{} 1\n255\n", usize::MAX / 3);
+        assert_eq!(ppm_to_bmp(huge.as_bytes()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "day-1")]
+    fn routes_requests() {
+        let dashboard = Dashboard {
+            port: 8025,
+            options: RunOptions {
+                data_dir: std::env::temp_dir()
+                    .join(format!("aoc-serve-test-{}", std::process::id())),
+                ..RunOptions::default()
+            },
+            last_runs: Mutex::default(),
+            running: Mutex::new(()),
+        };
+        let request_from = |method: &str, target: &str, headers: &str| {
+            let raw =
+                format!("{method} {target} HTTP/1.1\r\n{headers}Content-Length: 3\r\n\r\nx=1");
+            let request = read_request(&mut raw.as_bytes()).unwrap().unwrap();
+            dashboard.route(&request)
+        };
+        let request = |method: &str, target: &str| {
+            request_from(
+                method,
+                target,
+                "Host: localhost:8025\r\nOrigin: http://localhost:8025\r\n",
+            )
+        };
+
+        let index = request("GET", "/");
+        assert_eq!(index.status, "200 OK");
+        assert!(
+            String::from_utf8(index.body)
+                .unwrap()
+                .contains("Secret Entrance")
+        );
+        assert_eq!(request("GET", "/day/1").status, "200 OK");
+        assert_eq!(request("GET", "/day/13").status, "404 Not Found");
+        assert_eq!(
+            request("GET", "/day/1/viz/../../input.txt").status,
+            "404 Not Found"
+        );
+
+        for headers in [
+            "",
+            "Host: localhost:8025\r\nOrigin: http://example.com\r\n",
+            "Host: example.com:8025\r\n",
+            "Host: localhost:8000\r\n",
+        ] {
+            let response = request_from("POST", "/day/1/run?mode=e", headers);
+            assert_eq!(response.status, "403 Forbidden", "{headers:?}");
+        }
+        assert_eq!(
+            request_from("GET", "/", "Host: 127.0.0.1:8025\r\n").status,
+            "200 OK"
+        );
+
+        let run = request("POST", "/day/1/run?mode=e");
+        assert_eq!(run.location.as_deref(), Some("/day/1"));
+        let page = String::from_utf8(request("GET", "/day/1").body).unwrap();
+        let _ = fs::remove_dir_all(&dashboard.options.data_dir);
+        assert!(page.contains("built-in example"), "{page}");
+    }
+}
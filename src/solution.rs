@@ -0,0 +1,69 @@
+//! Solutions split into a parsing step and one step per part, so a day can register alternative
+//! implementations (e.g. a brute-force one) next to its main solution and compare them with `compare <day>`.
+
+use crate::answer::Answer;
+use crate::parse::ParseError;
+use crate::{Context, Part};
+
+pub trait Solution: Sync {
+    /// Parsed form of the puzzle input, shared by both parts
+    type Input;
+
+    /// Short name identifying this variant, e.g. `brute-force`
+    fn name(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(&self, input: &Self::Input, context: &Context) -> Answer;
+
+    fn part2(&self, input: &Self::Input, context: &Context) -> Answer;
+}
+
+/// Object-safe form of [`Solution`], used to register variants in [`DayInfo`](crate::DayInfo)
+pub trait Variant: Sync {
+    fn name(&self) -> &'static str;
+
+    /// Runs the solution like [`DayInfo::run`](crate::DayInfo::run) does
+    fn run(&self, context: &mut Context);
+}
+
+impl<S: Solution> Variant for S {
+    fn name(&self) -> &'static str {
+        Solution::name(self)
+    }
+
+    fn run(&self, context: &mut Context) {
+        let input = context.parse(|input| self.parse(input));
+        let answer = self.part1(&input, context);
+        context.result(answer);
+        if context.part == Part::Both {
+            let answer = self.part2(&input, context);
+            context.result(answer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::{solve, solve_with};
+
+    #[test]
+    fn variants_agree_on_examples() {
+        for (day_num, day) in days::enabled() {
+            // Day 12 has no usable example
+            if day.variants.is_empty() || day.example.is_empty() {
+                continue;
+            }
+            let expected = solve(day_num, day.example);
+            for variant in day.variants {
+                assert_eq!(
+                    solve_with(day_num, day.example, Some(*variant)),
+                    expected,
+                    "day {day_num}, variant {}",
+                    variant.name()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Line-by-line access to the input through [`Context::lines`](crate::Context::lines), so that days declared as
+//! [`streaming`](crate::DayInfo::streaming) can process inputs too large to be loaded in memory.
+
+use std::borrow::Cow;
+use std::io::BufRead;
+use std::str::Lines;
+
+/// Lines of the input along with their 1-based line numbers, like [`numbered_lines`](crate::parse::numbered_lines)
+pub enum InputLines<'a> {
+    /// Lines of an input that is already in memory
+    Memory { lines: Lines<'a>, number: usize },
+    /// Lines read from the input file one at a time
+    Reader {
+        reader: Box<dyn BufRead + 'a>,
+        number: usize,
+    },
+}
+
+impl<'a> InputLines<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::Memory {
+            lines: input.lines(),
+            number: 0,
+        }
+    }
+
+    pub fn from_reader(reader: Box<dyn BufRead + 'a>) -> Self {
+        Self::Reader { reader, number: 0 }
+    }
+}
+
+impl<'a> Iterator for InputLines<'a> {
+    type Item = (usize, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            InputLines::Memory { lines, number } => {
+                let line = lines.next()?;
+                *number += 1;
+                Some((*number, Cow::Borrowed(line)))
+            }
+            InputLines::Reader { reader, number } => {
+                let mut line = String::new();
+                let read = reader
+                    .read_line(&mut line)
+                    .unwrap_or_else(|err| panic!("could not read input: {err}"));
+                if read == 0 {
+                    return None;
+                }
+                // Same line endings as `str::lines`
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                *number += 1;
+                Some((*number, Cow::Owned(line)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::numbered_lines;
+
+    #[test]
+    fn reader_matches_memory() {
+        for input in ["", "a", "a\n", "a\r\nb\n\nc", "\n\n"] {
+            let expected = numbered_lines(input)
+                .map(|(number, line)| (number, line.to_string()))
+                .collect::<Vec<_>>();
+            let memory = InputLines::new(input)
+                .map(|(number, line)| (number, line.into_owned()))
+                .collect::<Vec<_>>();
+            let reader = InputLines::from_reader(Box::new(input.as_bytes()))
+                .map(|(number, line)| (number, line.into_owned()))
+                .collect::<Vec<_>>();
+            assert_eq!(memory, expected, "{input:?}");
+            assert_eq!(reader, expected, "{input:?}");
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Answer submission, with a local log in `data/<day>/submissions.txt` so known-wrong answers are never resubmitted.
+//!
+//! Each line of the log holds one answer the server gave a verdict on, as tab-separated fields:
+//! timestamp (seconds since the Unix epoch), level (1 or 2), verdict, answer.
+
+use crate::history::{escape, unescape};
+use crate::http::{HttpBackend, HttpError, Server};
+use displaythis::Display;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum Verdict {
+    #[display("correct")]
+    Correct,
+    #[display("incorrect")]
+    Incorrect,
+    #[display("too low")]
+    TooLow,
+    #[display("too high")]
+    TooHigh,
+    /// Rate limited, the answer was not checked
+    #[display("answered too recently, {0} left to wait")]
+    TooRecent(String),
+    /// The level was already solved, or part 1 isn't solved yet
+    #[display("wrong level")]
+    WrongLevel,
+    #[display("unrecognized response")]
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether this verdict says something about the answer itself, and is worth remembering
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::Incorrect | Verdict::TooLow | Verdict::TooHigh
+        )
+    }
+
+    fn code(&self) -> Option<&'static str> {
+        Some(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooLow => "too-low",
+            Verdict::TooHigh => "too-high",
+            _ => return None,
+        })
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "correct" => Verdict::Correct,
+            "incorrect" => Verdict::Incorrect,
+            "too-low" => Verdict::TooLow,
+            "too-high" => Verdict::TooHigh,
+            _ => return None,
+        })
+    }
+
+    /// Reads the verdict from the HTML page the server responds with
+    pub fn from_response(html: &str) -> Self {
+        if html.contains("That's the right answer") {
+            Verdict::Correct
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too low") {
+                Verdict::TooLow
+            } else if html.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else {
+                Verdict::Incorrect
+            }
+        } else if html.contains("You gave an answer too recently") {
+            // "You have 4m 12s left to wait."
+            let wait = html
+                .split_once(" left to wait")
+                .and_then(|(before, _)| before.rsplit_once("You have "))
+                .map_or("some time", |(_, wait)| wait);
+            Verdict::TooRecent(wait.to_string())
+        } else if html.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub level: u8,
+    pub answer: String,
+    /// Always a [final](Verdict::is_final) verdict
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.level,
+            self.verdict.code().unwrap(),
+            escape(&self.answer)
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Self {
+            timestamp: fields.next()?.parse().ok()?,
+            level: fields.next()?.parse().ok()?,
+            verdict: Verdict::from_code(fields.next()?)?,
+            answer: unescape(fields.next()?),
+        })
+    }
+}
+
+/// Why an answer is not sent to the server
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum Refusal {
+    #[display("level {0} was already solved with {1}")]
+    AlreadySolved(u8, String),
+    #[display("{0} was already submitted and was {1}")]
+    AlreadyRejected(String, Verdict),
+    #[display("{0} is not below {1}, which was too high")]
+    NotBelow(String, String),
+    #[display("{0} is not above {1}, which was too low")]
+    NotAbove(String, String),
+}
+
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("not submitted: {0}")]
+    Refused(Refusal),
+    #[error(transparent)]
+    Http(#[from] HttpError),
+    #[error("could not access submission log")]
+    Io(#[from] std::io::Error),
+    #[error("invalid submission log entry on line {0}")]
+    InvalidEntry(usize),
+}
+
+/// Checks an answer against the earlier verdicts for the same level
+pub fn check(log: &[Submission], level: u8, answer: &str) -> Result<(), Refusal> {
+    let earlier = log.iter().filter(|submission| submission.level == level);
+    let value = answer.parse::<i128>().ok();
+    for submission in earlier {
+        if submission.verdict == Verdict::Correct {
+            return Err(Refusal::AlreadySolved(level, submission.answer.clone()));
+        }
+        if submission.answer == answer {
+            return Err(Refusal::AlreadyRejected(
+                answer.to_string(),
+                submission.verdict.clone(),
+            ));
+        }
+        let (Some(value), Ok(bound)) = (value, submission.answer.parse::<i128>()) else {
+            continue;
+        };
+        match submission.verdict {
+            Verdict::TooHigh if value >= bound => {
+                return Err(Refusal::NotBelow(
+                    answer.to_string(),
+                    submission.answer.clone(),
+                ));
+            }
+            Verdict::TooLow if value <= bound => {
+                return Err(Refusal::NotAbove(
+                    answer.to_string(),
+                    submission.answer.clone(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Submits an answer unless the log already rules it out, logging the verdict if it is final
+pub fn submit(
+    backend: &dyn HttpBackend,
+    server: &Server,
+    day_dir: &Path,
+    day: u8,
+    level: u8,
+    answer: &str,
+) -> Result<Verdict, SubmitError> {
+    let log = load(day_dir)?;
+    check(&log, level, answer).map_err(SubmitError::Refused)?;
+    let url = format!("{}/answer", server.day_url(day));
+    let level_field = level.to_string();
+    let html = backend.post_form(
+        &url,
+        &server.cookie()?,
+        &[("level", &level_field), ("answer", answer)],
+    )?;
+    let verdict = Verdict::from_response(&html);
+    if verdict.is_final() {
+        let submission = Submission {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            level,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        };
+        append(day_dir, &submission)?;
+    }
+    Ok(verdict)
+}
+
+fn append(day_dir: &Path, submission: &Submission) -> Result<(), SubmitError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(day_dir.join("submissions.txt"))?;
+    writeln!(file, "{}", submission.to_line())?;
+    Ok(())
+}
+
+/// Loads all submissions of a day, oldest first; returns an empty list if nothing was submitted yet
+pub fn load(day_dir: &Path) -> Result<Vec<Submission>, SubmitError> {
+    let file = match File::open(day_dir.join("submissions.txt")) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.is_empty()))
+        .map(|(index, line)| {
+            Submission::from_line(&line?).ok_or(SubmitError::InvalidEntry(index + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::UreqBackend;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::{fs, thread};
+
+    fn submission(level: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1764565200,
+            level,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parses_responses() {
+        let wrong =
+            "<article><p>That's not the right answer; your answer is too low.</p></article>";
+        assert_eq!(Verdict::from_response(wrong), Verdict::TooLow);
+        let wait = "<p>You gave an answer too recently. You have 38s left to wait.</p>";
+        assert_eq!(
+            Verdict::from_response(wait),
+            Verdict::TooRecent("38s".to_string())
+        );
+        assert_eq!(Verdict::from_response("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let submission = submission(2, "#..\n.#.", Verdict::Incorrect);
+        assert_eq!(
+            Submission::from_line(&submission.to_line()),
+            Some(submission)
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = [
+            submission(1, "100", Verdict::TooHigh),
+            submission(1, "20", Verdict::TooLow),
+            submission(1, "abc", Verdict::Incorrect),
+            submission(2, "7", Verdict::Correct),
+        ];
+        assert_eq!(check(&log, 1, "50"), Ok(()));
+        assert!(matches!(
+            check(&log, 1, "100"),
+            Err(Refusal::AlreadyRejected(..))
+        ));
+        assert!(matches!(check(&log, 1, "150"), Err(Refusal::NotBelow(..))));
+        assert!(matches!(check(&log, 1, "3"), Err(Refusal::NotAbove(..))));
+        assert!(matches!(
+            check(&log, 1, "abc"),
+            Err(Refusal::AlreadyRejected(..))
+        ));
+        assert!(matches!(
+            check(&log, 2, "8"),
+            Err(Refusal::AlreadySolved(..))
+        ));
+    }
+
+    #[test]
+    fn submits_to_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server_thread = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            // Read until the whole form has arrived
+            while !String::from_utf8_lossy(&request).contains("answer=42") {
+                let read = stream.read(&mut buffer).unwrap();
+                assert_ne!(read, 0, "connection closed early");
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let body = "<article><p>That's the right answer!</p></article>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let day_dir = std::env::temp_dir().join(format!("aoc-submit-test-{}", std::process::id()));
+        fs::create_dir_all(&day_dir).unwrap();
+        let server = Server {
+            base_url,
+            year: 2025,
+            session: Some("abc".to_string()),
+        };
+        let verdict = submit(&UreqBackend::default(), &server, &day_dir, 3, 1, "42").unwrap();
+        let request = server_thread.join().unwrap();
+        let log = load(&day_dir).unwrap();
+        fs::remove_dir_all(&day_dir).unwrap();
+
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(request.starts_with("POST /2025/day/3/answer "));
+        assert!(request.contains("session=abc"));
+        assert!(request.contains("level=1&answer=42"));
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].verdict, Verdict::Correct);
+    }
+}
@@ -0,0 +1,5 @@
+//! Helpers shared between days
+
+pub mod numeric;
+pub mod random;
+pub mod union_find;
@@ -0,0 +1,547 @@
+use super::Integer;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Arbitrary-precision signed integer
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    /// Little-endian base 2^32 digits, without trailing zeros (so 0 is an empty vec)
+    magnitude: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseBigIntError {
+    #[error("cannot parse integer from empty string")]
+    Empty,
+    #[error("invalid digit found in string: {0:?}")]
+    InvalidDigit(char),
+}
+
+/// Largest power of 10 that fits in a digit, used for string conversions
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn signum(&self) -> i32 {
+        match (self.is_zero(), self.negative) {
+            (true, _) => 0,
+            (false, true) => -1,
+            (false, false) => 1,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            magnitude: self.magnitude.clone(),
+        }
+    }
+
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::from(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Returns the value as an `i64`, if it fits
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|n| n.try_into().ok())
+    }
+
+    /// Returns the value as an `i128`, if it fits
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 4 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &digit| (acc << 32) | digit as u128);
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            magnitude.try_into().ok()
+        }
+    }
+
+    /// Truncated division with remainder, like the primitive `/` and `%` operators.
+    /// Returns `None` if `divisor` is 0.
+    pub fn checked_div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+        Some((
+            Self::from_parts(self.negative != divisor.negative, quotient),
+            Self::from_parts(self.negative, remainder),
+        ))
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        trim(&mut magnitude);
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    fn add_signed(&self, rhs: &Self, rhs_negative: bool) -> Self {
+        if self.negative == rhs_negative {
+            return Self::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &rhs.magnitude),
+            );
+        }
+        match cmp_magnitude(&self.magnitude, &rhs.magnitude) {
+            Ordering::Equal => Self::zero(),
+            Ordering::Greater => Self::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &rhs.magnitude),
+            ),
+            Ordering::Less => {
+                Self::from_parts(rhs_negative, sub_magnitude(&rhs.magnitude, &self.magnitude))
+            }
+        }
+    }
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (index, &digit) in long.iter().enumerate() {
+        let sum = digit as u64 + *short.get(index).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry != 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// Calculates `a - b`, where `a >= b`
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (index, &digit) in a.iter().enumerate() {
+        let mut diff = digit as i64 - *b.get(index).unwrap_or(&0) as i64 - borrow;
+        borrow = if diff < 0 {
+            diff += 1 << 32;
+            1
+        } else {
+            0
+        };
+        result.push(diff as u32);
+    }
+    debug_assert_eq!(borrow, 0, "subtraction underflow");
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    trim(&mut result);
+    result
+}
+
+/// Divides by a single digit, returning the quotient and remainder
+fn div_rem_digit(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for (index, &digit) in a.iter().enumerate().rev() {
+        let current = (remainder << 32) | digit as u64;
+        quotient[index] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+/// Schoolbook binary long division; slow-ish, but simple and plenty fast for puzzle-sized numbers
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_magnitude(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (quotient, remainder) = div_rem_digit(a, b[0]);
+        return (
+            quotient,
+            if remainder == 0 {
+                Vec::new()
+            } else {
+                vec![remainder]
+            },
+        );
+    }
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::with_capacity(b.len() + 1);
+    for bit in (0..a.len() * 32).rev() {
+        // remainder = remainder * 2 + current bit
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for digit in remainder.iter_mut() {
+            let next_carry = *digit >> 31;
+            *digit = (*digit << 1) | carry;
+            carry = next_carry;
+        }
+        if carry != 0 {
+            remainder.push(carry);
+        }
+        if cmp_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = Vec::new();
+        while magnitude != 0 {
+            digits.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        Self::from_parts(value < 0, digits)
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(value: $t) -> Self {
+                    Self::from(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        if let Some(invalid) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseBigIntError::InvalidDigit(invalid));
+        }
+        let chunk = Self::from(DECIMAL_CHUNK);
+        let mut result = Self::zero();
+        // Parse in chunks of 9 digits, the first one possibly shorter
+        let first_len = match digits.len() % DECIMAL_CHUNK_DIGITS {
+            0 => DECIMAL_CHUNK_DIGITS,
+            len => len,
+        };
+        let mut rest = digits;
+        let mut len = first_len;
+        while !rest.is_empty() {
+            let (current, next) = rest.split_at(len);
+            result = &(&result * &chunk) + &Self::from(current.parse::<u32>().unwrap());
+            rest = next;
+            len = DECIMAL_CHUNK_DIGITS;
+        }
+        result.negative = negative && !result.is_zero();
+        Ok(result)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut chunks = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_digit(&magnitude, DECIMAL_CHUNK);
+            chunks.push(remainder);
+            magnitude = quotient;
+        }
+        let mut digits = match chunks.pop() {
+            None => String::from("0"),
+            Some(first) => first.to_string(),
+        };
+        for chunk in chunks.iter().rev() {
+            digits += &format!("{chunk:0width$}", width = DECIMAL_CHUNK_DIGITS);
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(mut self) -> Self::Output {
+        self.negative = !self.negative && !self.is_zero();
+        self
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_signed(rhs, rhs.negative)
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.add_signed(rhs, !rhs.negative && !rhs.is_zero())
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.magnitude, &rhs.magnitude),
+        )
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div_rem(rhs)
+            .expect("attempt to divide by zero")
+            .0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.checked_div_rem(rhs)
+            .expect("attempt to calculate the remainder with a divisor of zero")
+            .1
+    }
+}
+
+macro_rules! forward_owned_op {
+    ($($trait:ident, $method:ident);*) => {
+        $(
+            impl $trait for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, rhs: Self) -> Self::Output {
+                    (&self).$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_op!(Add, add; Sub, sub; Mul, mul; Div, div; Rem, rem);
+
+impl Integer for BigInt {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::from(1)
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.checked_div_rem(rhs).map(|(quotient, _)| quotient)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        self.checked_div_rem(rhs).map(|(_, remainder)| remainder)
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        for s in [
+            "0",
+            "7",
+            "-42",
+            "4294967296",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("+000123").to_string(), "123");
+        assert_eq!(format!("{:>6}", big("-12")), "   -12");
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!(
+            "12a".parse::<BigInt>(),
+            Err(ParseBigIntError::InvalidDigit('a'))
+        );
+    }
+
+    #[test]
+    fn matches_primitive_arithmetic() {
+        let values = [
+            0i128,
+            1,
+            -1,
+            7,
+            -13,
+            1 << 40,
+            -(1 << 63) + 5,
+            i64::MAX as i128,
+        ];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!((&x + &y).to_i128(), Some(a + b), "{a} + {b}");
+                assert_eq!((&x - &y).to_i128(), Some(a - b), "{a} - {b}");
+                assert_eq!((&x * &y).to_i128(), Some(a * b), "{a} * {b}");
+                assert_eq!(x.cmp(&y), a.cmp(&b), "{a} cmp {b}");
+                if b != 0 {
+                    assert_eq!((&x / &y).to_i128(), Some(a / b), "{a} / {b}");
+                    assert_eq!((&x % &y).to_i128(), Some(a % b), "{a} % {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_values() {
+        let factorial = (1..=30).fold(BigInt::from(1), |acc, n| acc * BigInt::from(n));
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(
+            &factorial / &(1..=28).fold(BigInt::from(1), |acc, n| acc * BigInt::from(n)),
+            BigInt::from(30 * 29)
+        );
+        assert_eq!(
+            BigInt::from(2).pow(100).to_string(),
+            "1267650600228229401496703205376"
+        );
+        assert_eq!(BigInt::from(2).pow(127).to_i128(), None);
+        assert_eq!((-BigInt::from(2).pow(127)).to_i128(), Some(i128::MIN));
+        assert_eq!(BigInt::from(i64::MAX).to_i64(), Some(i64::MAX));
+        assert_eq!((BigInt::from(i64::MAX) + BigInt::from(1)).to_i64(), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Exact integer & rational arithmetic, for days that can't afford floats or silent overflow
+
+mod big_int;
+mod rational;
+
+pub use big_int::{BigInt, ParseBigIntError};
+pub use rational::{BigRational, Rational, Rational64, Rational128};
+
+use std::fmt::{Debug, Display};
+
+/// Signed integer type usable with the generic helpers in this module and with [`Rational`].
+///
+/// All arithmetic is checked; `None` is returned on overflow or division by zero.
+/// Division truncates towards zero, like the primitive integer types.
+pub trait Integer: Clone + Ord + Debug + Display {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    fn checked_div(&self, rhs: &Self) -> Option<Self>;
+    fn checked_rem(&self, rhs: &Self) -> Option<Self>;
+    fn checked_neg(&self) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
+
+    fn checked_abs(&self) -> Option<Self> {
+        if self.is_negative() {
+            self.checked_neg()
+        } else {
+            Some(self.clone())
+        }
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *rhs)
+                }
+
+                fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *rhs)
+                }
+
+                fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_div(*self, *rhs)
+                }
+
+                fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_rem(*self, *rhs)
+                }
+
+                fn checked_neg(&self) -> Option<Self> {
+                    <$t>::checked_neg(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize);
+
+/// Calculate the greatest common divisor; the result is never negative.
+///
+/// Panics if the result doesn't fit in `T` (e.g. `gcd(i64::MIN, 0)`).
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("attempt to calculate gcd with overflow")
+}
+
+/// Calculate the greatest common divisor, returning `None` if the result doesn't fit in `T`
+pub fn checked_gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while !b.is_zero() {
+        let r = a.checked_rem(&b)?;
+        a = b;
+        b = r;
+    }
+    a.checked_abs()
+}
+
+/// Calculate the least common multiple; the result is never negative.
+///
+/// Panics if the result doesn't fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("attempt to calculate lcm with overflow")
+}
+
+/// Calculate the least common multiple, returning `None` if the result doesn't fit in `T`
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a.is_zero() || b.is_zero() {
+        return Some(T::zero());
+    }
+    let gcd = checked_gcd(a.clone(), b.clone())?;
+    // Divide first so the intermediate result is never larger than the final one
+    a.checked_div(&gcd)?.checked_mul(&b)?.checked_abs()
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` such that `a*x + b*y = g`, where `g = gcd(a, b)`.
+///
+/// Panics on overflow.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let overflow = || panic!("attempt to calculate extended gcd with overflow");
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r.checked_div(&r).unwrap_or_else(overflow);
+        let step = |old: &T, new: &T| {
+            old.checked_sub(&q.checked_mul(new).unwrap_or_else(overflow))
+                .unwrap_or_else(overflow)
+        };
+        (old_r, r) = (r.clone(), step(&old_r, &r));
+        (old_x, x) = (x.clone(), step(&old_x, &x));
+        (old_y, y) = (y.clone(), step(&old_y, &y));
+    }
+    if old_r.is_negative() {
+        let neg = |n: T| n.checked_neg().unwrap_or_else(overflow);
+        (neg(old_r), neg(old_x), neg(old_y))
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Calculate the inverse of `a` modulo `m`, as a value in `0..m`.
+/// Returns `None` if `a` and `m` are not coprime, or if `m` is not positive.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    if m <= T::zero() {
+        return None;
+    }
+    let a = a.checked_rem(&m)?;
+    let (g, x, _) = extended_gcd(a, m.clone());
+    if g != T::one() {
+        return None;
+    }
+    let x = x.checked_rem(&m)?;
+    if x.is_negative() {
+        x.checked_add(&m)
+    } else {
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(12, 15), 3);
+        assert_eq!(gcd(4, 34), 2);
+        assert_eq!(gcd(12345, 1), 1);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, -7), 7);
+        assert_eq!(checked_gcd(i64::MIN, 0), None);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-3, 5), 15);
+        assert_eq!(lcm(0, 5), 0);
+        // a * b would overflow, but the lcm itself fits
+        assert_eq!(lcm(i32::MAX - 1, 2), i32::MAX - 1);
+        assert_eq!(checked_lcm(i32::MAX, i32::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (17, 0), (0, -5), (35, 15)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "gcd of {a}, {b}");
+            assert_eq!(a * x + b * y, g, "coefficients of {a}, {b}");
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(5, 0), None);
+    }
+}
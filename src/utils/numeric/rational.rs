@@ -0,0 +1,378 @@
+use super::{BigInt, Integer, checked_gcd};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Exact fraction, always stored in lowest terms with a positive denominator.
+///
+/// The `checked_*` methods return `None` on overflow (or division by zero), while the operators panic instead,
+/// mirroring how primitive integers behave in debug mode - except that overflow is never silently ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational<T: Integer> {
+    numer: T,
+    denom: T,
+}
+
+pub type Rational64 = Rational<i64>;
+pub type Rational128 = Rational<i128>;
+pub type BigRational = Rational<BigInt>;
+
+impl<T: Integer> Rational<T> {
+    /// Create a new fraction, reducing it to lowest terms. Panics if `denom` is 0 or on overflow.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "denominator cannot be 0");
+        Self::checked_new(numer, denom).expect("attempt to create fraction with overflow")
+    }
+
+    /// Create a new fraction, reducing it to lowest terms.
+    /// Returns `None` if `denom` is 0 or the reduced fraction cannot be represented.
+    pub fn checked_new(numer: T, denom: T) -> Option<Self> {
+        if denom.is_zero() {
+            return None;
+        }
+        let gcd = checked_gcd(numer.clone(), denom.clone())?;
+        let (mut numer, mut denom) = (numer.checked_div(&gcd)?, denom.checked_div(&gcd)?);
+        if denom.is_negative() {
+            numer = numer.checked_neg()?;
+            denom = denom.checked_neg()?;
+        }
+        Some(Self { numer, denom })
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::one(),
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::one()
+    }
+
+    /// Returns the value as an integer, or `None` if it has a fractional part
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then(|| self.numer.clone())
+    }
+
+    /// Largest integer less than or equal to this value
+    pub fn floor(&self) -> T {
+        let quotient = self.numer.checked_div(&self.denom).unwrap();
+        if self.is_negative() && !self.is_integer() {
+            // Cannot overflow: the quotient of a non-integer is never T::MIN
+            quotient.checked_sub(&T::one()).unwrap()
+        } else {
+            quotient
+        }
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        // a/b + c/d = (a*(d/g) + c*(b/g)) / (b/g*d), where g = gcd(b, d)
+        let gcd = checked_gcd(self.denom.clone(), rhs.denom.clone())?;
+        let lhs_mul = rhs.denom.checked_div(&gcd)?;
+        let rhs_mul = self.denom.checked_div(&gcd)?;
+        let numer = self
+            .numer
+            .checked_mul(&lhs_mul)?
+            .checked_add(&rhs.numer.checked_mul(&rhs_mul)?)?;
+        Self::checked_new(numer, self.denom.checked_mul(&lhs_mul)?)
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.checked_add(&rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        // Cross-reduce first to keep intermediate values small
+        let gcd1 = checked_gcd(self.numer.clone(), rhs.denom.clone())?;
+        let gcd2 = checked_gcd(rhs.numer.clone(), self.denom.clone())?;
+        if gcd1.is_zero() || gcd2.is_zero() {
+            // One of the numerators is 0 (denominators never are)
+            return Some(Self::zero());
+        }
+        let numer = self
+            .numer
+            .checked_div(&gcd1)?
+            .checked_mul(&rhs.numer.checked_div(&gcd2)?)?;
+        let denom = self
+            .denom
+            .checked_div(&gcd2)?
+            .checked_mul(&rhs.denom.checked_div(&gcd1)?)?;
+        Self::checked_new(numer, denom)
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.checked_mul(&rhs.checked_recip()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom.clone(),
+        })
+    }
+
+    /// Returns `1 / self`, or `None` if `self` is 0
+    pub fn checked_recip(&self) -> Option<Self> {
+        Self::checked_new(self.denom.clone(), self.numer.clone())
+    }
+
+    pub fn recip(&self) -> Self {
+        assert!(!self.is_zero(), "attempt to calculate the reciprocal of 0");
+        self.checked_recip()
+            .expect("attempt to calculate reciprocal with overflow")
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<T: Integer> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl<T: Integer> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl<T: Integer> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare using continued fractions, which avoids the overflow that cross-multiplying would cause
+        let (mut a, mut b) = (self.clone(), other.clone());
+        let mut flipped = false;
+        loop {
+            let (floor_a, floor_b) = (a.floor(), b.floor());
+            if floor_a != floor_b {
+                let ordering = floor_a.cmp(&floor_b);
+                return if flipped {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+            }
+            // Same integer part, compare the fractional parts. Taken as the remainder, since `floor * denom`
+            // can overflow for numerators close to `T::MIN`; the denominator is positive, so this can't overflow.
+            let frac = |r: &Self| {
+                let rem = r.numer.checked_rem(&r.denom).unwrap();
+                Self {
+                    numer: if rem.is_negative() {
+                        rem.checked_add(&r.denom).unwrap()
+                    } else {
+                        rem
+                    },
+                    denom: r.denom.clone(),
+                }
+            };
+            let (frac_a, frac_b) = (frac(&a), frac(&b));
+            match (frac_a.is_zero(), frac_b.is_zero()) {
+                (true, true) => return Ordering::Equal,
+                (true, false) => {
+                    return if flipped {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    };
+                }
+                (false, true) => {
+                    return if flipped {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    };
+                }
+                (false, false) => {
+                    // Both fractional parts are in (0, 1), so their reciprocals are > 1 and compare in reverse.
+                    // Already in lowest terms with positive numerators, so they are swapped without reducing.
+                    a = Self {
+                        numer: frac_a.denom,
+                        denom: frac_a.numer,
+                    };
+                    b = Self {
+                        numer: frac_b.denom,
+                        denom: frac_b.numer,
+                    };
+                    flipped = !flipped;
+                }
+            }
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_op {
+    ($trait:ident, $method:ident, $checked:ident, $msg:literal) => {
+        impl<T: Integer> $trait for Rational<T> {
+            type Output = Rational<T>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<T: Integer> $trait for &Rational<T> {
+            type Output = Rational<T>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, checked_add, "attempt to add with overflow");
+impl_op!(Sub, sub, checked_sub, "attempt to subtract with overflow");
+impl_op!(Mul, mul, checked_mul, "attempt to multiply with overflow");
+impl_op!(
+    Div,
+    div,
+    checked_div,
+    "attempt to divide by zero or with overflow"
+);
+
+impl<T: Integer> Neg for Rational<T> {
+    type Output = Rational<T>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<T: Integer> Neg for &Rational<T> {
+    type Output = Rational<T>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numer: i64, denom: i64) -> Rational64 {
+        Rational::new(numer, denom)
+    }
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(r(2, 4), r(1, 2));
+        assert_eq!(r(3, -6), r(-1, 2));
+        assert_eq!(*r(0, -5).denom(), 1);
+        assert_eq!(r(-6, 3).to_integer(), Some(-2));
+        assert_eq!(r(1, 3).to_integer(), None);
+        assert_eq!(Rational64::checked_new(1, 0), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(-4, 9), r(-3, 2));
+        assert_eq!(r(0, 1) * r(5, 7), r(0, 1));
+        assert_eq!(r(7, 2).floor(), 3);
+        assert_eq!(r(-7, 2).floor(), -4);
+        assert_eq!(r(-3, 4).to_string(), "-3/4");
+        assert_eq!(r(8, 4).to_string(), "2");
+    }
+
+    #[test]
+    fn overflow_is_detected() {
+        let big = Rational64::from_integer(i64::MAX);
+        assert_eq!(big.checked_add(&Rational::one()), None);
+        assert_eq!(big.checked_mul(&r(2, 1)), None);
+        // Cross-reduction keeps this in range
+        assert_eq!(big.checked_mul(&r(3, i64::MAX)), Some(r(3, 1)));
+        assert_eq!(Rational64::zero().checked_recip(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn operators_panic_on_overflow() {
+        let _ = Rational64::from_integer(i64::MIN) * r(-1, 1);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(22, 7) > r(355, 113));
+        assert_eq!(r(2, 4).cmp(&r(1, 2)), Ordering::Equal);
+        // Cross-multiplying these would overflow
+        assert!(r(i64::MAX - 1, i64::MAX) > r(i64::MAX - 2, i64::MAX - 1));
+    }
+
+    #[test]
+    fn ordering_extreme_values() {
+        let values = [
+            r(i64::MIN, 1),
+            r(i64::MIN + 1, 3),
+            r(i64::MIN + 1, i64::MAX),
+            r(-1, i64::MAX),
+            r(0, 1),
+            r(1, i64::MAX),
+            r(i64::MAX - 1, i64::MAX),
+            r(i64::MAX, 3),
+            r(i64::MAX, 1),
+        ];
+        for (i, a) in values.iter().enumerate() {
+            for (j, b) in values.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn big_rational() {
+        let third = BigRational::new(BigInt::from(1), BigInt::from(3));
+        let sum = &(&third + &third) + &third;
+        assert_eq!(sum, BigRational::one());
+        let huge = BigRational::from_integer(BigInt::from(i128::MAX));
+        assert_eq!(
+            (&huge * &huge).to_string(),
+            (BigInt::from(i128::MAX) * BigInt::from(i128::MAX)).to_string()
+        );
+    }
+}
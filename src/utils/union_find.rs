@@ -0,0 +1,119 @@
+/// Disjoint set (union-find) over the elements `0..len`, with path compression and union by size
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// Size of each component, only meaningful for root elements
+    size: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    /// Create a new disjoint set where every element is in its own component
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    /// Total number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint components
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Returns the representative element of the component containing `element`
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Path compression: point every element on the way directly to the root
+        let mut current = element;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the components containing `a` and `b`. Returns `false` if they were already in the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // Attach the smaller tree under the larger one
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.component_count -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same component
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the component containing `element`
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// Sizes of all components, in no particular order
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parent[element] == element)
+            .map(|root| self.size[root])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.component_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 4));
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.component_size(2), 4);
+        assert_eq!(set.component_size(5), 1);
+        let mut sizes = set.component_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 1, 4]);
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 100_000;
+        let mut set = DisjointSet::new(n);
+        for i in 1..n {
+            set.union(i - 1, i);
+        }
+        assert_eq!(set.component_count(), 1);
+        assert_eq!(set.component_size(0), n);
+        assert_eq!(set.find(0), set.find(n - 1));
+    }
+}
@@ -0,0 +1,79 @@
+//! Declarative input formats: rules a day's input must follow, checked before the day runs so that all
+//! violations are reported at once, with their line numbers, instead of a panic at the first one.
+
+use crate::parse::{ParseError, numbered_lines};
+
+/// A structural precondition of an input
+pub enum Rule {
+    /// The input is a grid with as many columns as rows
+    SquareGrid,
+    /// A check of each line, describing what's wrong with it
+    EachLine(fn(line: &str) -> Result<(), String>),
+    /// A check of the whole input, returning every violation
+    Input(fn(input: &str) -> Vec<ParseError>),
+}
+
+impl Rule {
+    fn check(&self, input: &str) -> Vec<ParseError> {
+        match self {
+            Rule::SquareGrid => {
+                let size = input.lines().count();
+                if size == 0 {
+                    return vec![ParseError::new(1, "empty grid")];
+                }
+                numbered_lines(input)
+                    .filter(|(_, line)| line.chars().count() != size)
+                    .map(|(number, line)| {
+                        let message = format!(
+                            "{} columns in a grid of {size} rows, expected a square grid",
+                            line.chars().count()
+                        );
+                        ParseError::new(number, message)
+                    })
+                    .collect()
+            }
+            Rule::EachLine(check) => numbered_lines(input)
+                .filter_map(|(number, line)| {
+                    check(line)
+                        .err()
+                        .map(|message| ParseError::new(number, message))
+                })
+                .collect(),
+            Rule::Input(check) => check(input),
+        }
+    }
+}
+
+/// Checks the input against all rules, returning the violations ordered by line
+pub fn validate(rules: &[Rule], input: &str) -> Vec<ParseError> {
+    let mut violations = rules
+        .iter()
+        .flat_map(|rule| rule.check(input))
+        .collect::<Vec<_>>();
+    violations.sort_by_key(|violation| violation.line);
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_all_violations() {
+        let rules = [
+            Rule::SquareGrid,
+            Rule::EachLine(|line| {
+                if line.contains('x') {
+                    Err("no x allowed".to_string())
+                } else {
+                    Ok(())
+                }
+            }),
+        ];
+        assert!(validate(&rules, "..\n..").is_empty());
+        let violations = validate(&rules, "x..\n..\n...");
+        let lines = violations.iter().map(|v| v.line).collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2]);
+        assert_eq!(violations[0].message, "no x allowed");
+    }
+}
@@ -0,0 +1,203 @@
+//! Visualization frames emitted by solutions through [`Context::visualize`](crate::Context::visualize).
+//!
+//! When enabled, each frame is written to its own file under `data/<day>/viz/<examples|full>/`,
+//! numbered in the order the frames were emitted.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+pub enum Frame {
+    /// Plain text, usually an ASCII grid; saved as `.txt`
+    Text(String),
+    /// Vector image; saved as `.svg`
+    Svg(Svg),
+    /// Raster image; saved as `.ppm`
+    Image(Image),
+}
+
+impl Frame {
+    /// Builds a text frame from a grid of characters, one row per line
+    pub fn grid<R, C>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = char>,
+    {
+        let mut text = String::new();
+        for row in rows {
+            text.extend(row);
+            text.push('\n');
+        }
+        Frame::Text(text)
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Frame::Text(_) => "txt",
+            Frame::Svg(_) => "svg",
+            Frame::Image(_) => "ppm",
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Frame::Text(text) => text.clone().into_bytes(),
+            Frame::Svg(svg) => svg.render().into_bytes(),
+            Frame::Image(image) => image.to_ppm(),
+        }
+    }
+}
+
+/// Minimal SVG builder; coordinates are in user units, scaled to fit the view box
+#[derive(Debug, Clone)]
+pub struct Svg {
+    view_box: (f64, f64, f64, f64),
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// Creates an empty image showing the area between `(min_x, min_y)` and `(min_x + width, min_y + height)`
+    pub fn new(min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        Self {
+            view_box: (min_x, min_y, width, height),
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn polygon(&mut self, points: &[(f64, f64)], fill: &str, stroke: &str) -> &mut Self {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.elements.push(format!(
+            r#"<polygon points="{points}" fill="{fill}" stroke="{stroke}" vector-effect="non-scaling-stroke"/>"#
+        ));
+        self
+    }
+
+    pub fn rect(
+        &mut self,
+        (x, y): (f64, f64),
+        (width, height): (f64, f64),
+        fill: &str,
+        stroke: &str,
+    ) -> &mut Self {
+        self.elements.push(format!(
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{fill}" stroke="{stroke}" vector-effect="non-scaling-stroke"/>"#
+        ));
+        self
+    }
+
+    pub fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), stroke: &str) -> &mut Self {
+        self.elements.push(format!(
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{stroke}" vector-effect="non-scaling-stroke"/>"#
+        ));
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let (min_x, min_y, width, height) = self.view_box;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{min_x} {min_y} {width} {height}" width="800" height="800" preserveAspectRatio="xMidYMid meet">"#
+        );
+        svg.push('\n');
+        for element in &self.elements {
+            writeln!(svg, "  {element}").unwrap();
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+pub type Rgb = [u8; 3];
+
+/// Raster image, saved in the binary PPM format
+#[derive(Debug, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) out of bounds"
+        );
+        self.pixels[y * self.width + x] = color;
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+}
+
+/// Writes the frames of a single day run, owned by the [`Context`](crate::Context)
+pub(crate) struct Visualizer {
+    /// Output directory, `None` if visualization is disabled
+    dir: Option<PathBuf>,
+    frame_count: Cell<u32>,
+}
+
+impl Visualizer {
+    pub(crate) fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            frame_count: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Saves a frame, returning the path it was written to
+    pub(crate) fn save(&self, name: &str, frame: Frame) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        let index = self.frame_count.get();
+        if index == 0 {
+            // Remove frames left over from a previous run
+            if dir.exists() {
+                fs::remove_dir_all(dir).unwrap();
+            }
+            fs::create_dir_all(dir).unwrap();
+        }
+        self.frame_count.set(index + 1);
+        let path = dir.join(format!("{index:04}-{name}.{}", frame.extension()));
+        fs::write(&path, frame.to_bytes()).unwrap();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_frame() {
+        let Frame::Text(text) = Frame::grid(["ab".chars(), "cd".chars()]) else {
+            unreachable!()
+        };
+        assert_eq!(text, "ab\ncd\n");
+    }
+
+    #[test]
+    fn ppm_header() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set(1, 0, [255, 0, 10]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\xff\0\x0a");
+    }
+}